@@ -0,0 +1,46 @@
+use wrg_snake_core::{GameMode, GameStats};
+
+mod personal_best;
+mod settings;
+mod storage;
+
+pub use self::personal_best::PersonalBest;
+pub use self::settings::Settings;
+pub use self::storage::{LocalStorage, Storage};
+
+/// Everything kept about the player between visits, every change is saved right away
+pub struct Profile {
+    settings: Settings,
+    storage: Box<Storage>,
+}
+
+impl Profile {
+    pub fn load(storage: Box<Storage>) -> Profile {
+        Profile {
+            settings: Settings::load(&*storage),
+            storage,
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: Settings) {
+        self.settings = settings;
+        self.settings.save(&mut *self.storage);
+    }
+
+    /// Updates the personal best of `mode` with a finished game, returns the
+    /// personal best from before the game so the game can be compared against it,
+    /// or `None` if the mode doesn't keep a personal best
+    pub fn record_game(&mut self, mode: GameMode, score: usize, stats: &GameStats) -> Option<PersonalBest> {
+        let previous = PersonalBest::load(&*self.storage, mode)?;
+        let mut personal_best = previous;
+        personal_best.update(score, stats);
+        if personal_best != previous {
+            personal_best.save(&mut *self.storage, mode);
+        }
+        Some(previous)
+    }
+}
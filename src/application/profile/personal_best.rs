@@ -0,0 +1,88 @@
+use wrg_snake_core::{GameMode, GameStats};
+use super::storage::{Storage, load_json, save_json};
+
+/// The best of each statistic over every game played in one mode, they
+/// don't need to come from the same game
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersonalBest {
+    pub score: usize,
+    pub duration: f64,
+    pub foods_eaten: usize,
+    pub max_speed: f64,
+    pub tiles_visited: usize,
+}
+
+impl PersonalBest {
+    /// `None` for modes without a personal best
+    pub fn load(storage: &Storage, mode: GameMode) -> Option<PersonalBest> {
+        Self::storage_key(mode).map(|key| load_json(storage, key).unwrap_or_default())
+    }
+
+    pub fn save(&self, storage: &mut Storage, mode: GameMode) {
+        if let Some(key) = Self::storage_key(mode) {
+            save_json(storage, key, self);
+        }
+    }
+
+    /// Each mode keeps its own personal best saved as JSON, except zen which is only practice
+    fn storage_key(mode: GameMode) -> Option<&'static str> {
+        match mode {
+            GameMode::Classic => Some("wrg-snake-personal-best"),
+            GameMode::TimeAttack => Some("wrg-snake-personal-best-time-attack"),
+            GameMode::Zen => None,
+        }
+    }
+
+    /// Raises every best the game beat to the game's value
+    pub fn update(&mut self, score: usize, stats: &GameStats) {
+        self.score = self.score.max(score);
+        self.duration = self.duration.max(stats.duration);
+        self.foods_eaten = self.foods_eaten.max(stats.foods_eaten);
+        self.max_speed = self.max_speed.max(stats.max_speed);
+        self.tiles_visited = self.tiles_visited.max(stats.tiles_visited);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::storage::MemoryStorage;
+
+    #[test]
+    fn update_keeps_the_best_of_each() {
+        let mut best = PersonalBest {
+            score: 10,
+            duration: 30.0,
+            foods_eaten: 10,
+            max_speed: 12.0,
+            tiles_visited: 50,
+        };
+
+        let stats = GameStats {
+            duration: 45.0,
+            foods_eaten: 4,
+            max_speed: 9.0,
+            turns: 20,
+            tiles_visited: 80,
+        };
+        best.update(4, &stats);
+
+        assert_eq!(best, PersonalBest {
+            score: 10,
+            duration: 45.0,
+            foods_eaten: 10,
+            max_speed: 12.0,
+            tiles_visited: 80,
+        });
+
+        let mut storage = MemoryStorage::default();
+        assert_eq!(PersonalBest::load(&storage, GameMode::Classic), Some(PersonalBest::default()));
+        best.save(&mut storage, GameMode::Classic);
+        assert_eq!(PersonalBest::load(&storage, GameMode::Classic), Some(best));
+        assert_eq!(PersonalBest::load(&storage, GameMode::TimeAttack), Some(PersonalBest::default()));
+
+        best.save(&mut storage, GameMode::Zen);
+        assert_eq!(PersonalBest::load(&storage, GameMode::Zen), None);
+    }
+}
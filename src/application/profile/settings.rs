@@ -0,0 +1,217 @@
+use wrg_2d::{Grid, IntVector2};
+use wrg_snake_render::ThemeKind;
+use super::super::snake_game::{Config, Controls};
+use super::storage::{Storage, load_json, save_json};
+
+/// Key the settings are saved under, as JSON
+const STORAGE_KEY: &str = "wrg-snake-settings";
+
+/// Preferences the player picks in the settings scene, they're applied
+/// on top of the host's `Config` whenever a game starts
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: ThemeKind,
+    pub controls: Controls,
+    pub grid_size: GridSize,
+    pub speed: Speed,
+    /// Turning effects on only plays the effects the host has enabled
+    pub effects: bool,
+    pub show_grid_markers: bool,
+}
+
+/// Size of the grid compared to the one in the host's `Config`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GridSize {
+    Small,
+    Normal,
+    Large,
+}
+
+/// Speed of the snake compared to the one in the host's `Config`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Speed {
+    Slow,
+    Normal,
+    Fast,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            theme: ThemeKind::Light,
+            controls: Controls::Wasd,
+            grid_size: GridSize::Normal,
+            speed: Speed::Normal,
+            effects: true,
+            show_grid_markers: true,
+        }
+    }
+}
+
+impl Settings {
+    /// Loads the saved settings, falling back to the defaults if nothing
+    /// was saved or it can't be read anymore
+    pub fn load(storage: &Storage) -> Settings {
+        load_json(storage, STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self, storage: &mut Storage) {
+        save_json(storage, STORAGE_KEY, self);
+    }
+
+    /// The config a game should be started with, given the host's `config`.
+    /// The start position is scaled along with the grid, unless the snake
+    /// wouldn't fit in it anymore, then the grid size is left alone.
+    pub fn apply(&self, config: &Config) -> Config {
+        let mut applied = config.clone();
+        applied.controls = self.controls;
+        applied.rules.speed *= self.speed.multiplier();
+        applied.effects.enabled &= self.effects;
+        applied.show_grid_markers &= self.show_grid_markers;
+
+        let grid = self.grid_size.scale(&config.grid);
+        let with_grid_size = Config {
+            grid,
+            start_position: IntVector2 {
+                x: self.grid_size.scale_length(config.start_position.x as u16).min(grid.width - 1) as i32,
+                y: self.grid_size.scale_length(config.start_position.y as u16).min(grid.height - 1) as i32,
+            },
+            ..applied.clone()
+        };
+
+        if with_grid_size.validate().is_ok() {
+            with_grid_size
+        } else {
+            applied
+        }
+    }
+}
+
+impl GridSize {
+    pub const ALL: [GridSize; 3] = [GridSize::Small, GridSize::Normal, GridSize::Large];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GridSize::Small => "Small",
+            GridSize::Normal => "Normal",
+            GridSize::Large => "Large",
+        }
+    }
+
+    /// The size after this one, wrapping around to the first
+    pub fn next(&self) -> GridSize {
+        let index = Self::ALL.iter().position(|size| size == self).expect("ALL contains every GridSize");
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn scale(&self, grid: &Grid) -> Grid {
+        Grid::new(self.scale_length(grid.width).max(1), self.scale_length(grid.height).max(1))
+    }
+
+    /// Scales a number of cells, rounding to the nearest cell
+    fn scale_length(&self, length: u16) -> u16 {
+        let (numerator, denominator) = match self {
+            GridSize::Small => (2, 3),
+            GridSize::Normal => (1, 1),
+            GridSize::Large => (4, 3),
+        };
+
+        ((length as u32 * numerator + denominator / 2) / denominator) as u16
+    }
+}
+
+impl Speed {
+    pub const ALL: [Speed; 3] = [Speed::Slow, Speed::Normal, Speed::Fast];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Speed::Slow => "Slow",
+            Speed::Normal => "Normal",
+            Speed::Fast => "Fast",
+        }
+    }
+
+    /// The speed after this one, wrapping around to the first
+    pub fn next(&self) -> Speed {
+        let index = Self::ALL.iter().position(|speed| speed == self).expect("ALL contains every Speed");
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn multiplier(&self) -> f64 {
+        match self {
+            Speed::Slow => 0.75,
+            Speed::Normal => 1.0,
+            Speed::Fast => 1.5,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::storage::MemoryStorage;
+
+    #[test]
+    fn settings_are_saved_and_loaded() {
+        let mut storage = MemoryStorage::default();
+        assert_eq!(Settings::load(&storage), Settings::default());
+
+        let settings = Settings {
+            theme: ThemeKind::Dark,
+            controls: Controls::Both,
+            grid_size: GridSize::Large,
+            speed: Speed::Fast,
+            effects: false,
+            show_grid_markers: false,
+        };
+        settings.save(&mut storage);
+        assert_eq!(Settings::load(&storage), settings);
+    }
+
+    #[test]
+    fn unreadable_settings_use_defaults() {
+        let mut storage = MemoryStorage::default();
+        storage.save(STORAGE_KEY, "not json");
+        assert_eq!(Settings::load(&storage), Settings::default());
+
+        // settings saved before a field existed keep the rest
+        storage.save(STORAGE_KEY, r#"{ "theme": "high_contrast" }"#);
+        assert_eq!(Settings::load(&storage), Settings { theme: ThemeKind::HighContrast, ..Settings::default() });
+    }
+
+    #[test]
+    fn apply_changes_the_config() {
+        let config = Config::default();
+        let settings = Settings {
+            controls: Controls::Arrows,
+            grid_size: GridSize::Small,
+            speed: Speed::Slow,
+            effects: false,
+            show_grid_markers: false,
+            ..Settings::default()
+        };
+
+        let applied = settings.apply(&config);
+        assert_eq!(applied.controls, Controls::Arrows);
+        assert_eq!(applied.grid, Grid::new(10, 10));
+        assert_eq!(applied.start_position, IntVector2 { x: 0, y: 9 });
+        assert_eq!(applied.rules.speed, 0.75);
+        assert!(!applied.effects.enabled);
+        assert!(!applied.show_grid_markers);
+        assert!(applied.validate().is_ok());
+
+        assert_eq!(Settings::default().apply(&config).grid, config.grid);
+        assert_eq!(GridSize::Large.scale(&config.grid), Grid::new(20, 20));
+    }
+
+    #[test]
+    fn apply_keeps_the_grid_if_the_snake_does_not_fit() {
+        let mut config = Config::default();
+        config.start_length = 200;
+        let settings = Settings { grid_size: GridSize::Small, ..Settings::default() };
+        assert_eq!(settings.apply(&config).grid, config.grid);
+    }
+}
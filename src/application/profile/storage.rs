@@ -0,0 +1,66 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+use wasm_bindgen::prelude::*;
+#[cfg(test)]
+use std::collections::HashMap;
+
+/// Somewhere small strings can be kept between visits to the page
+pub trait Storage {
+    fn load(&self, key: &str) -> Option<String>;
+
+    /// Saving can fail, for example when the browser blocks storage, but
+    /// nothing is lost besides the saved value so failures are ignored
+    fn save(&mut self, key: &str, value: &str);
+}
+
+#[wasm_bindgen]
+extern {
+    #[wasm_bindgen(catch, js_namespace = localStorage, js_name = getItem)]
+    fn local_storage_get_item(key: &str) -> Result<Option<String>, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = localStorage, js_name = setItem)]
+    fn local_storage_set_item(key: &str, value: &str) -> Result<(), JsValue>;
+}
+
+/// The browser's `localStorage`
+pub struct LocalStorage;
+
+impl Storage for LocalStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        local_storage_get_item(key).ok().and_then(|value| value)
+    }
+
+    fn save(&mut self, key: &str, value: &str) {
+        let _ = local_storage_set_item(key, value);
+    }
+}
+
+/// Loads a value saved with `save_json()`, `None` if nothing was saved or it can't be read anymore
+pub fn load_json<T: DeserializeOwned>(storage: &Storage, key: &str) -> Option<T> {
+    storage.load(key).and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn save_json<T: Serialize>(storage: &mut Storage, key: &str, value: &T) {
+    if let Ok(json) = serde_json::to_string(value) {
+        storage.save(key, &json);
+    }
+}
+
+/// Keeps values for as long as it's alive
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStorage {
+    values: HashMap<String, String>,
+}
+
+#[cfg(test)]
+impl Storage for MemoryStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+
+    fn save(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+}
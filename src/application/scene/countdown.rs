@@ -0,0 +1,69 @@
+use wasm_rgame::delegate_prelude::*;
+use wasm_rgame_ui::{Text, TextConfig, Vector2, Transform, TransformVector};
+use super::{Scene, SceneContext, SceneTransition};
+use super::super::snake_game::SnakeGameHandle;
+
+const COUNTDOWN_SECONDS : u32 = 3;
+
+/// Pushed over a game that's about to start, which stays paused until the countdown is done
+pub struct CountdownScene {
+    game: SpawnHandle<SnakeGameHandle>,
+    start: f64,
+    /// The number being shown and its text
+    shown: Option<(u32, SpawnHandles)>,
+}
+
+impl CountdownScene {
+    pub fn new(game: SpawnHandle<SnakeGameHandle>) -> CountdownScene {
+        CountdownScene {
+            game,
+            start: 0.0,
+            shown: None,
+        }
+    }
+
+    fn spawn_number(context: &mut SceneContext, number: u32) -> SpawnHandles {
+        let text_handle = context.spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.5, }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: number.to_string(),
+            font_size: 90.0,
+            color: context.theme.theme().text,
+            render_order: 5,
+        }));
+
+        SpawnHandles::new().with(text_handle)
+    }
+}
+
+impl Scene for CountdownScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        self.game.set_paused(true);
+        self.start = context.total_s;
+    }
+
+    fn exit(&mut self, _context: &mut SceneContext) {
+        self.shown = None;
+        self.game.set_paused(false);
+    }
+
+    fn tick(&mut self, context: &mut SceneContext) -> SceneTransition {
+        let elapsed = context.total_s - self.start;
+        if elapsed >= COUNTDOWN_SECONDS as f64 {
+            return SceneTransition::Pop;
+        }
+
+        let number = COUNTDOWN_SECONDS - elapsed as u32;
+        let needs_update = match self.shown {
+            Some((shown, _)) => shown != number,
+            None => true,
+        };
+        if needs_update {
+            self.shown = Some((number, Self::spawn_number(context, number)));
+        }
+
+        SceneTransition::None
+    }
+}
@@ -0,0 +1,68 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_rgame::Canvas;
+use wasm_rgame::delegate_prelude::*;
+use wrg_snake_render::ThemeHandle;
+
+/// Covers the whole canvas while switching between scenes, drawn
+/// on top of everything the scenes spawned
+pub struct FadeOverlay {
+    handle: FadeHandle,
+    theme: ThemeHandle,
+}
+
+#[derive(Clone)]
+pub struct FadeHandle {
+    /// How much (0.0 - 1.0) the canvas is covered
+    amount: Rc<Cell<f32>>,
+}
+
+impl FadeOverlay {
+    pub fn new(theme: ThemeHandle) -> FadeOverlay {
+        FadeOverlay {
+            handle: FadeHandle { amount: Rc::new(Cell::new(0.0)) },
+            theme,
+        }
+    }
+}
+
+impl Delegate for FadeOverlay {
+    fn tick(
+        &mut self,
+        _context: &mut ApplicationContext,
+        _key_manager: &KeyManager,
+        _mouse_state: &MouseState,
+        _delegate_spawner: &mut DelegateSpawner,
+    ) {}
+
+    fn render(&self, graphics: &mut Graphics) {
+        let amount = self.handle.amount.get();
+        if amount <= 0.0 {
+            return;
+        }
+
+        // fade to the overlay color, which is picked to stand out from the game
+        let mut color = self.theme.theme().overlay;
+        color[3] = (255.0 * amount) as u8;
+
+        let canvas = Canvas::instance();
+        graphics.draw_rect(0.0, 0.0, canvas.width() as f32, canvas.height() as f32, color, 0.0);
+    }
+
+    // render in front of everything
+    fn render_order(&self) -> i32 { 100 }
+}
+
+impl SpawnableDelegate for FadeOverlay {
+    type Handle = FadeHandle;
+
+    fn handle(&self) -> Self::Handle {
+        self.handle.clone()
+    }
+}
+
+impl FadeHandle {
+    pub fn set_amount(&self, amount: f32) {
+        self.amount.set(amount.max(0.0).min(1.0));
+    }
+}
@@ -0,0 +1,152 @@
+use wasm_rgame::delegate_prelude::*;
+use wasm_rgame_ui::{Text, TextConfig, Vector2, Transform, TransformVector};
+use wrg_snake_core::{DeathCause, GameMode, GameStats};
+use wrg_snake_render::Theme;
+use super::{RunningScene, Scene, SceneContext, SceneTransition, draw_overlay};
+use super::super::profile::PersonalBest;
+use super::super::snake_game::{GameState, SnakeGameHandle};
+use super::super::ui::Menu;
+
+/// Shows how the game went over the finished game, which stays on screen behind it
+pub struct GameOverScene {
+    /// Keeps the finished game spawned
+    game: SpawnHandle<SnakeGameHandle>,
+    /// Playing again starts a game in the same mode
+    mode: GameMode,
+    score: usize,
+    stats: GameStats,
+    /// The personal best before this game, if the mode keeps one
+    previous_best: Option<PersonalBest>,
+    handles: Option<GameOverHandles>,
+}
+
+struct GameOverHandles {
+    menu: Menu,
+    _others: SpawnHandles,
+}
+
+impl GameOverScene {
+    pub fn new(
+        game: SpawnHandle<SnakeGameHandle>,
+        mode: GameMode,
+        score: usize,
+        stats: GameStats,
+        previous_best: Option<PersonalBest>,
+    ) -> GameOverScene
+    {
+        GameOverScene {
+            game,
+            mode,
+            score,
+            stats,
+            previous_best,
+            handles: None,
+        }
+    }
+
+    /// One line per statistic, compared against the personal best where that makes sense
+    fn stat_lines(&self) -> Vec<String> {
+        let stats = &self.stats;
+        let best = self.previous_best.as_ref();
+        vec![
+            compared("Score", self.score, best.map(|best| best.score), |score| score.to_string()),
+            compared("Time", stats.duration, best.map(|best| best.duration), |duration| format!("{:.1}s", duration)),
+            compared("Foods eaten", stats.foods_eaten, best.map(|best| best.foods_eaten), |foods| foods.to_string()),
+            compared("Top speed", stats.max_speed, best.map(|best| best.max_speed), |speed| format!("{:.1} cells/s", speed)),
+            compared("Tiles visited", stats.tiles_visited, best.map(|best| best.tiles_visited), |tiles| tiles.to_string()),
+            format!("Turns: {}", stats.turns),
+            death_cause_text(self.game.state()).to_string(),
+        ]
+    }
+}
+
+/// "label: value" followed by the personal best, or by a note that the value beat it
+fn compared<T: PartialOrd + Copy, F: Fn(T) -> String>(label: &str, value: T, best: Option<T>, format: F) -> String {
+    match best {
+        Some(best) if value > best => format!("{}: {} (new best!)", label, format(value)),
+        Some(best) => format!("{}: {} (best {})", label, format(value), format(best)),
+        None => format!("{}: {}", label, format(value)),
+    }
+}
+
+fn death_cause_text(state: GameState) -> &'static str {
+    match state {
+        GameState::Finished { cause: DeathCause::SelfCollision { .. }, .. } => "The snake ran into itself",
+        GameState::Finished { cause: DeathCause::FilledGrid, .. } => "The snake filled the whole grid!",
+        GameState::Finished { cause: DeathCause::Hazard { .. }, .. } => "The snake ran into a hazard",
+        GameState::Finished { cause: DeathCause::OutOfTime, .. } => "Time's up!",
+        // only zen games can be ended while the snake is alive
+        GameState::Running => "Practice over",
+    }
+}
+
+impl Scene for GameOverScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        let spawner = &mut *context.spawner;
+        let colors = context.theme.theme();
+        let mut menu = Menu::new();
+        menu.add_button(
+            spawner,
+            &colors,
+            Vector2 { x: 0.5, y: 0.10, },
+            Vector2 { x: 150.0, y: 34.0, },
+            "Play Again".to_string(),
+            20.0,
+        );
+
+        let header_handle = spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.76, }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: "Game Over".to_string(),
+            font_size: 50.0,
+            color: colors.text,
+            render_order: 5,
+        }));
+
+        let mut others = SpawnHandles::new().with(header_handle);
+        for (index, line) in self.stat_lines().into_iter().enumerate() {
+            let line_handle = spawner.spawn(Text::new(Transform::new(
+                TransformVector::Relative(Vector2 { x: 0.5, y: 0.60 - index as f32 * 0.06 }),
+                TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+                Vector2 { x: 0.5, y: 0.5, },
+            ), TextConfig {
+                text: line,
+                font_size: 13.0,
+                color: colors.text,
+                render_order: 5,
+            }));
+            others = others.with(line_handle);
+        }
+
+        self.handles = Some(GameOverHandles {
+            menu,
+            _others: others,
+        });
+    }
+
+    fn exit(&mut self, _context: &mut SceneContext) {
+        self.handles = None;
+    }
+
+    fn tick(&mut self, context: &mut SceneContext) -> SceneTransition {
+        // "Play Again" is the only button
+        let activated = match self.handles {
+            Some(ref mut handles) => handles.menu.tick(context.key_manager),
+            None => None,
+        };
+
+        match activated {
+            Some(_) => SceneTransition::Replace(Box::new(RunningScene::new(self.mode))),
+            None => SceneTransition::None,
+        }
+    }
+
+    fn render(&self, graphics: &mut Graphics, theme: &Theme) {
+        draw_overlay(graphics, theme);
+        if let Some(ref handles) = self.handles {
+            handles.menu.render(graphics, theme);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use wasm_rgame::Canvas;
+use wasm_rgame::delegate_prelude::*;
+use wrg_snake_render::{Theme, ThemeHandle};
+use super::Config;
+use super::profile::Profile;
+
+mod countdown;
+mod fade;
+mod game_over;
+mod pause;
+mod running;
+mod settings;
+mod title_screen;
+
+pub use self::countdown::CountdownScene;
+pub use self::game_over::GameOverScene;
+pub use self::pause::PauseScene;
+pub use self::running::RunningScene;
+pub use self::settings::SettingsScene;
+pub use self::title_screen::TitleScreenScene;
+
+use self::fade::{FadeHandle, FadeOverlay};
+
+const INVALID_CONFIG: &str = "Config is validated before the application is created";
+
+/// Seconds it takes to fade out of a scene, and again to fade into the next one
+const FADE_DURATION : f64 = 0.25;
+
+/// Everything a scene can use while entering, exiting or ticking
+pub struct SceneContext<'a> {
+    /// The host's config, games should be started with `game_config()`
+    /// so the player's settings are applied
+    pub config: &'a Config,
+    pub theme: &'a ThemeHandle,
+    pub profile: &'a mut Profile,
+    pub spawner: &'a mut DelegateSpawner,
+    pub key_manager: &'a KeyManager,
+    /// Seconds since the application started
+    pub total_s: f64,
+}
+
+impl<'a> SceneContext<'a> {
+    /// The config for a new game, with the player's settings applied
+    pub fn game_config(&self) -> Config {
+        self.profile.settings().apply(self.config)
+    }
+}
+
+/// What a scene wants to happen to the scene stack after it ticked
+pub enum SceneTransition {
+    None,
+    /// Put a scene on top of this one, this scene stays alive underneath
+    Push(Box<Scene>),
+    /// Remove this scene, uncovering the scene below it
+    Pop,
+    /// Remove this scene and put another in its place, fading between them
+    Replace(Box<Scene>),
+}
+
+enum Fade {
+    /// Covering the current scene, `next` replaces it once it's fully covered
+    Out {
+        start: f64,
+        next: Box<Scene>,
+    },
+    /// Uncovering the scene that was just switched to
+    In {
+        start: f64,
+    },
+}
+
+/// One screen of the application, like the title screen or a running game.
+///
+/// Scenes spawn their delegates in `enter()` and hold on to their handles, so
+/// everything a scene spawned is despawned once the scene is dropped.
+pub trait Scene {
+    /// Called when the scene is added to the stack
+    fn enter(&mut self, context: &mut SceneContext);
+
+    /// Called right before the scene is removed from the stack
+    fn exit(&mut self, _context: &mut SceneContext) {}
+
+    /// Called every tick while the scene is at the top of the stack
+    fn tick(&mut self, context: &mut SceneContext) -> SceneTransition;
+
+    /// Draws anything that isn't drawn by a spawned delegate, every
+    /// scene in the stack is rendered from the bottom up
+    fn render(&self, _graphics: &mut Graphics, _theme: &Theme) {}
+}
+
+/// The scenes that are alive, only the top one is ticked
+pub struct SceneStack {
+    scenes: Vec<Box<Scene>>,
+    fade: Option<Fade>,
+    fade_overlay: Option<SpawnHandle<FadeHandle>>,
+}
+
+impl SceneStack {
+    pub fn new() -> SceneStack {
+        SceneStack {
+            scenes: Vec::new(),
+            fade: None,
+            fade_overlay: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    pub fn push(&mut self, mut scene: Box<Scene>, context: &mut SceneContext) {
+        scene.enter(context);
+        self.scenes.push(scene);
+    }
+
+    pub fn pop(&mut self, context: &mut SceneContext) {
+        if let Some(mut scene) = self.scenes.pop() {
+            scene.exit(context);
+        }
+    }
+
+    pub fn replace(&mut self, scene: Box<Scene>, context: &mut SceneContext) {
+        self.pop(context);
+        self.push(scene, context);
+    }
+
+    /// Ticks the top scene and applies the transition it asks for
+    pub fn tick(&mut self, context: &mut SceneContext) {
+        if self.fade_overlay.is_none() {
+            self.fade_overlay = Some(context.spawner.spawn(FadeOverlay::new(context.theme.clone())));
+        }
+
+        if !self.tick_fade(context) {
+            // nothing reacts to input while the scene is fading out
+            return;
+        }
+
+        let transition = match self.scenes.last_mut() {
+            Some(scene) => scene.tick(context),
+            None => return,
+        };
+
+        match transition {
+            SceneTransition::None => (),
+            SceneTransition::Push(scene) => self.push(scene, context),
+            SceneTransition::Pop => self.pop(context),
+            SceneTransition::Replace(next) => self.fade = Some(Fade::Out { start: context.total_s, next }),
+        }
+    }
+
+    /// Advances the fade between scenes, returns whether the top scene should be ticked
+    fn tick_fade(&mut self, context: &mut SceneContext) -> bool {
+        let (amount, tick_scene) = match self.fade.take() {
+            Some(Fade::Out { start, next }) => {
+                let progress = (context.total_s - start) / FADE_DURATION;
+                if progress < 1.0 {
+                    self.fade = Some(Fade::Out { start, next });
+                } else {
+                    // switch scenes while they're hidden
+                    self.replace(next, context);
+                    self.fade = Some(Fade::In { start: context.total_s });
+                }
+                (progress, false)
+            },
+            Some(Fade::In { start }) => {
+                let progress = (context.total_s - start) / FADE_DURATION;
+                if progress < 1.0 {
+                    self.fade = Some(Fade::In { start });
+                }
+                (1.0 - progress, true)
+            },
+            None => (0.0, true),
+        };
+
+        if let Some(ref fade_overlay) = self.fade_overlay {
+            fade_overlay.set_amount(amount as f32);
+        }
+        tick_scene
+    }
+
+    pub fn render(&self, graphics: &mut Graphics, theme: &Theme) {
+        for scene in &self.scenes {
+            scene.render(graphics, theme);
+        }
+    }
+}
+
+/// Draws a transparent overlay over the game, for menus shown on top of it
+fn draw_overlay(graphics: &mut Graphics, theme: &Theme) {
+    let canvas = Canvas::instance();
+    graphics.draw_rect(
+        0.0,
+        0.0,
+        canvas.width() as f32,
+        canvas.height() as f32,
+        theme.overlay,
+        0.0,
+    );
+}
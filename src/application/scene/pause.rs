@@ -0,0 +1,181 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_rgame::delegate_prelude::*;
+use wasm_rgame_ui::{Text, TextConfig, Vector2, Transform, TransformVector};
+use wrg_snake_render::Theme;
+use super::{Scene, SceneContext, SceneTransition, draw_overlay};
+use super::running::PAUSE_KEYS;
+use super::super::snake_game::SnakeGameHandle;
+use super::super::ui::{KeyPress, Menu};
+
+/// Pushed over a running game, which stays paused until this scene is popped
+/// with "Resume", an ended game is left paused for the scene below to finish
+pub struct PauseScene {
+    game: SnakeGameHandle,
+    /// Set when the player asks to end a game that can't end on its own
+    end_game: Option<Rc<Cell<bool>>>,
+    ending: bool,
+    resume_key: KeyPress,
+    handles: Option<PauseHandles>,
+}
+
+struct PauseHandles {
+    menu: Menu,
+    end_game_button: Option<usize>,
+    _others: SpawnHandles,
+}
+
+impl PauseScene {
+    /// With `end_game` the menu gets an "End Game" button, which sets it and pops the scene
+    pub fn new(game: SnakeGameHandle, end_game: Option<Rc<Cell<bool>>>) -> PauseScene {
+        PauseScene {
+            game,
+            end_game,
+            ending: false,
+            resume_key: KeyPress::new(PAUSE_KEYS),
+            handles: None,
+        }
+    }
+
+    /// Pops the scene, either resuming the game or asking for it to end
+    fn leave(&mut self, end: bool) -> SceneTransition {
+        if end {
+            if let Some(ref end_game) = self.end_game {
+                end_game.set(true);
+                self.ending = true;
+            }
+        }
+        SceneTransition::Pop
+    }
+
+    /// Lets the game run again, unless it's being ended
+    fn release_game(&self) {
+        if !self.ending {
+            self.game.set_paused(false);
+        }
+    }
+}
+
+impl Scene for PauseScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        self.game.set_paused(true);
+
+        let spawner = &mut *context.spawner;
+        let colors = context.theme.theme();
+        let mut menu = Menu::new();
+        menu.add_button(
+            spawner,
+            &colors,
+            Vector2 { x: 0.5, y: 0.30, },
+            Vector2 { x: 150.0, y: 40.0, },
+            "Resume".to_string(),
+            23.0,
+        );
+
+        let end_game_button = self.end_game.as_ref().map(|_| menu.add_button(
+            spawner,
+            &colors,
+            Vector2 { x: 0.5, y: 0.17, },
+            Vector2 { x: 150.0, y: 30.0, },
+            "End Game".to_string(),
+            16.0,
+        ));
+
+        let header_handle = spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.63, }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: "Paused".to_string(),
+            font_size: 60.0,
+            color: colors.text,
+            render_order: 5,
+        }));
+
+        self.handles = Some(PauseHandles {
+            menu,
+            end_game_button,
+            _others: SpawnHandles::new().with(header_handle),
+        });
+    }
+
+    fn exit(&mut self, _context: &mut SceneContext) {
+        self.handles = None;
+        self.release_game();
+    }
+
+    fn tick(&mut self, context: &mut SceneContext) -> SceneTransition {
+        let resume_pressed = self.resume_key.pressed(context.key_manager);
+        let (activated, end_game_button) = match self.handles {
+            Some(ref mut handles) => (handles.menu.tick(context.key_manager), handles.end_game_button),
+            None => (None, None),
+        };
+
+        if resume_pressed || activated.is_some() {
+            self.leave(activated.is_some() && activated == end_game_button)
+        } else {
+            SceneTransition::None
+        }
+    }
+
+    fn render(&self, graphics: &mut Graphics, theme: &Theme) {
+        draw_overlay(graphics, theme);
+        if let Some(ref handles) = self.handles {
+            handles.menu.render(graphics, theme);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wrg_snake_core::XorShiftRandom;
+    use wrg_snake_render::{ThemeHandle, ThemeKind};
+    use super::super::super::snake_game::{Config, SnakeGame};
+
+    #[test]
+    fn ending_the_game_keeps_it_paused() {
+        let mut game = SnakeGame::new(Config::default(), ThemeHandle::new(ThemeKind::Light)).unwrap();
+        let mut random = XorShiftRandom::new(1);
+        let handle = game.handle();
+        for i in 0..4 {
+            game.advance(i as f64 * 0.2, None, &mut random);
+        }
+        assert!(game.moves() > 0);
+
+        let end_game = Rc::new(Cell::new(false));
+        let mut scene = PauseScene::new(handle.clone(), Some(end_game.clone()));
+        // what entering the scene does
+        handle.set_paused(true);
+        let moves = game.moves();
+
+        match scene.leave(true) {
+            SceneTransition::Pop => (),
+            _ => panic!("ending the game should pop the pause scene"),
+        }
+        scene.release_game();
+        for i in 4..8 {
+            game.advance(i as f64 * 0.2, None, &mut random);
+        }
+        assert!(end_game.get());
+        assert_eq!(game.moves(), moves);
+    }
+
+    #[test]
+    fn resuming_unpauses_the_game() {
+        let mut game = SnakeGame::new(Config::default(), ThemeHandle::new(ThemeKind::Light)).unwrap();
+        let mut random = XorShiftRandom::new(1);
+        let handle = game.handle();
+        let end_game = Rc::new(Cell::new(false));
+        let mut scene = PauseScene::new(handle.clone(), Some(end_game.clone()));
+        handle.set_paused(true);
+
+        scene.leave(false);
+        scene.release_game();
+        for i in 0..4 {
+            game.advance(i as f64 * 0.2, None, &mut random);
+        }
+        assert!(!end_game.get());
+        assert!(game.moves() > 0);
+    }
+}
@@ -0,0 +1,128 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_rgame::key_codes;
+use wasm_rgame::delegate_prelude::*;
+use wasm_rgame_ui::{Text, TextConfig, Vector2, Transform, TransformVector};
+use super::{INVALID_CONFIG, CountdownScene, GameOverScene, PauseScene, Scene, SceneContext, SceneTransition};
+use super::super::snake_game::{SnakeGame, GameMode, GameState, SnakeGameHandle};
+use super::super::ui::KeyPress;
+
+pub const PAUSE_KEYS : &[u8] = &[key_codes::P, key_codes::ESCAPE];
+
+/// Seconds to keep showing a finished game before the game over menu
+const GAME_OVER_DELAY : f64 = 1.0;
+
+/// A game the player is controlling
+pub struct RunningScene {
+    mode: GameMode,
+    game: Option<SpawnHandle<SnakeGameHandle>>,
+    pause_key: KeyPress,
+    counted_down: bool,
+    /// Set by the pause menu to end a zen game, which can't end on its own
+    end_requested: Rc<Cell<bool>>,
+    /// The whole seconds left shown in time attack, and their text
+    time_left_shown: Option<(u32, SpawnHandles)>,
+}
+
+impl RunningScene {
+    pub fn new(mode: GameMode) -> RunningScene {
+        RunningScene {
+            mode,
+            game: None,
+            pause_key: KeyPress::new(PAUSE_KEYS),
+            counted_down: false,
+            end_requested: Rc::new(Cell::new(false)),
+            time_left_shown: None,
+        }
+    }
+
+    /// Shows the seconds left at the top in time attack, only respawning the text when they change
+    fn update_time_left(&mut self, context: &mut SceneContext) {
+        let seconds = match self.game.as_ref().and_then(|game| game.time_left()) {
+            Some(time_left) => time_left.ceil() as u32,
+            None => return,
+        };
+
+        if let Some((shown, _)) = self.time_left_shown {
+            if shown == seconds {
+                return;
+            }
+        }
+
+        let text_handle = context.spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.95, }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: format!("Time: {}", seconds),
+            font_size: 18.0,
+            color: context.theme.theme().text,
+            render_order: 5,
+        }));
+        self.time_left_shown = Some((seconds, SpawnHandles::new().with(text_handle)));
+    }
+}
+
+impl Scene for RunningScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        let mut config = context.game_config();
+        config.rules.mode = self.mode;
+        let game = SnakeGame::new(config, context.theme.clone()).expect(INVALID_CONFIG);
+        let game = context.spawner.spawn(game);
+        // hold the game still until the countdown is pushed on the first tick
+        game.set_paused(true);
+        self.game = Some(game);
+        self.update_time_left(context);
+    }
+
+    fn exit(&mut self, _context: &mut SceneContext) {
+        self.game = None;
+        self.time_left_shown = None;
+    }
+
+    fn tick(&mut self, context: &mut SceneContext) -> SceneTransition {
+        self.update_time_left(context);
+        let game = match self.game {
+            Some(ref game) => game,
+            None => return SceneTransition::None,
+        };
+
+        if !self.counted_down {
+            self.counted_down = true;
+            return SceneTransition::Push(Box::new(CountdownScene::new(game.clone())));
+        }
+
+        if self.end_requested.get() {
+            // the snake is still alive, keep it still behind the game over menu
+            game.set_paused(true);
+            let score = game.size().saturating_sub(context.config.start_length);
+            let game_over = GameOverScene::new(game.clone(), self.mode, score, game.stats(), None);
+            return SceneTransition::Replace(Box::new(game_over));
+        }
+
+        let pause_pressed = self.pause_key.pressed(context.key_manager);
+        match game.state() {
+            GameState::Running => {
+                if pause_pressed {
+                    let end_game = match self.mode {
+                        GameMode::Zen => Some(self.end_requested.clone()),
+                        _ => None,
+                    };
+                    return SceneTransition::Push(Box::new(PauseScene::new((**game).clone(), end_game)));
+                }
+            },
+            GameState::Finished { size, time, .. } => {
+                // only show the game over menu if the game is over for a little while
+                if game.time() - time > GAME_OVER_DELAY {
+                    let score = size.saturating_sub(context.config.start_length);
+                    let stats = game.stats();
+                    let previous_best = context.profile.record_game(self.mode, score, &stats);
+                    let game_over = GameOverScene::new(game.clone(), self.mode, score, stats, previous_best);
+                    return SceneTransition::Replace(Box::new(game_over));
+                }
+            },
+        }
+
+        SceneTransition::None
+    }
+}
@@ -0,0 +1,168 @@
+use wasm_rgame::key_codes;
+use wasm_rgame::delegate_prelude::*;
+use wasm_rgame_ui::{Text, TextConfig, Vector2, Transform, TransformVector};
+use wrg_snake_render::Theme;
+use super::{Scene, SceneContext, SceneTransition, TitleScreenScene, draw_overlay};
+use super::super::profile::Settings;
+use super::super::ui::{KeyPress, Menu};
+
+const BACK_KEYS : &[u8] = &[key_codes::ESCAPE];
+
+/// Lets the player change their settings, each setting is a button
+/// which switches to the next value when activated
+pub struct SettingsScene {
+    handles: Option<SettingsHandles>,
+    back_key: KeyPress,
+}
+
+struct SettingsHandles {
+    menu: Menu,
+    back_button: usize,
+    _others: SpawnHandles,
+}
+
+/// The settings in the order their buttons are shown
+#[derive(Clone, Copy)]
+enum Setting {
+    Theme,
+    Controls,
+    GridSize,
+    Speed,
+    Effects,
+    GridMarkers,
+}
+
+impl Setting {
+    const ALL: [Setting; 6] = [
+        Setting::Theme,
+        Setting::Controls,
+        Setting::GridSize,
+        Setting::Speed,
+        Setting::Effects,
+        Setting::GridMarkers,
+    ];
+
+    fn label(&self, settings: &Settings) -> String {
+        fn on_off(on: bool) -> &'static str {
+            if on { "On" } else { "Off" }
+        }
+
+        match self {
+            Setting::Theme => format!("Theme: {}", settings.theme.name()),
+            Setting::Controls => format!("Controls: {}", settings.controls.name()),
+            Setting::GridSize => format!("Grid Size: {}", settings.grid_size.name()),
+            Setting::Speed => format!("Speed: {}", settings.speed.name()),
+            Setting::Effects => format!("Effects: {}", on_off(settings.effects)),
+            Setting::GridMarkers => format!("Grid Markers: {}", on_off(settings.show_grid_markers)),
+        }
+    }
+
+    /// Switches the setting to its next value
+    fn cycle(&self, settings: &mut Settings) {
+        match self {
+            Setting::Theme => settings.theme = settings.theme.next(),
+            Setting::Controls => settings.controls = settings.controls.next(),
+            Setting::GridSize => settings.grid_size = settings.grid_size.next(),
+            Setting::Speed => settings.speed = settings.speed.next(),
+            Setting::Effects => settings.effects = !settings.effects,
+            Setting::GridMarkers => settings.show_grid_markers = !settings.show_grid_markers,
+        }
+    }
+}
+
+impl SettingsScene {
+    pub fn new() -> SettingsScene {
+        SettingsScene {
+            handles: None,
+            back_key: KeyPress::new(BACK_KEYS),
+        }
+    }
+}
+
+impl Scene for SettingsScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        let spawner = &mut *context.spawner;
+        let colors = context.theme.theme();
+        let settings = *context.profile.settings();
+        let mut menu = Menu::new();
+
+        for (index, setting) in Setting::ALL.iter().enumerate() {
+            menu.add_button(
+                spawner,
+                &colors,
+                Vector2 { x: 0.5, y: 0.72 - index as f32 * 0.1, },
+                Vector2 { x: 200.0, y: 26.0, },
+                setting.label(&settings),
+                13.0,
+            );
+        }
+
+        let back_button = menu.add_button(
+            spawner,
+            &colors,
+            Vector2 { x: 0.5, y: 0.08, },
+            Vector2 { x: 150.0, y: 30.0, },
+            "Back".to_string(),
+            16.0,
+        );
+
+        let header_handle = spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.87, }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: "Settings".to_string(),
+            font_size: 40.0,
+            color: colors.text,
+            render_order: 5,
+        }));
+
+        self.handles = Some(SettingsHandles {
+            menu,
+            back_button,
+            _others: SpawnHandles::new().with(header_handle),
+        });
+    }
+
+    fn exit(&mut self, _context: &mut SceneContext) {
+        self.handles = None;
+    }
+
+    fn tick(&mut self, context: &mut SceneContext) -> SceneTransition {
+        let back_pressed = self.back_key.pressed(context.key_manager);
+        let (activated, back_button, focused) = match self.handles {
+            Some(ref mut handles) => (
+                handles.menu.tick(context.key_manager),
+                handles.back_button,
+                handles.menu.focused(),
+            ),
+            None => return SceneTransition::None,
+        };
+
+        if back_pressed || activated == Some(back_button) {
+            return SceneTransition::Replace(Box::new(TitleScreenScene::new()));
+        }
+
+        if let Some(setting) = activated.and_then(|index| Setting::ALL.get(index)) {
+            let mut settings = *context.profile.settings();
+            setting.cycle(&mut settings);
+            context.profile.set_settings(settings);
+            context.theme.set_kind(settings.theme);
+
+            // rebuild the menu to show the new value, and the new theme
+            self.enter(context);
+            if let Some(ref mut handles) = self.handles {
+                handles.menu.focus(focused);
+            }
+        }
+
+        SceneTransition::None
+    }
+
+    fn render(&self, graphics: &mut Graphics, theme: &Theme) {
+        draw_overlay(graphics, theme);
+        if let Some(ref handles) = self.handles {
+            handles.menu.render(graphics, theme);
+        }
+    }
+}
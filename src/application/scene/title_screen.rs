@@ -0,0 +1,144 @@
+use wasm_rgame::delegate_prelude::*;
+use wasm_rgame_ui::{Text, TextConfig, Vector2, Transform, TransformVector};
+use wrg_snake_render::Theme;
+use super::{INVALID_CONFIG, RunningScene, Scene, SceneContext, SceneTransition, SettingsScene, draw_overlay};
+use super::super::snake_game::{GameMode, SnakeGame};
+use super::super::ui::Menu;
+
+/// The menu shown when the application starts, with a demo game playing behind it
+pub struct TitleScreenScene {
+    handles: Option<TitleScreenHandles>,
+}
+
+struct TitleScreenHandles {
+    menu: Menu,
+    play_button: usize,
+    time_attack_button: usize,
+    settings_button: usize,
+    _others: SpawnHandles,
+}
+
+impl TitleScreenScene {
+    pub fn new() -> TitleScreenScene {
+        TitleScreenScene { handles: None }
+    }
+}
+
+impl Scene for TitleScreenScene {
+    fn enter(&mut self, context: &mut SceneContext) {
+        let mut title_config = context.game_config();
+        title_config.input_allowed = false;
+        // the demo shouldn't end just because time ran out
+        title_config.rules.mode = GameMode::Classic;
+        let controls = title_config.controls;
+
+        let spawner = &mut *context.spawner;
+        let game_handle = spawner.spawn(SnakeGame::new(title_config, context.theme.clone()).expect(INVALID_CONFIG));
+        let colors = context.theme.theme();
+        let mut menu = Menu::new();
+        let play_button = menu.add_button(
+            spawner,
+            &colors,
+            Vector2 { x: 0.5, y: 0.33, },
+            Vector2 { x: 150.0, y: 36.0, },
+            "Play".to_string(),
+            21.0,
+        );
+
+        let time_attack_button = menu.add_button(
+            spawner,
+            &colors,
+            Vector2 { x: 0.5, y: 0.21, },
+            Vector2 { x: 150.0, y: 30.0, },
+            "Time Attack".to_string(),
+            16.0,
+        );
+
+        let settings_button = menu.add_button(
+            spawner,
+            &colors,
+            Vector2 { x: 0.5, y: 0.09, },
+            Vector2 { x: 150.0, y: 26.0, },
+            "Settings".to_string(),
+            13.0,
+        );
+
+        let title_handle = spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.66, }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: "SNAKE-RS".to_string(),
+            font_size: 60.0,
+            color: colors.text,
+            render_order: 5,
+        }));
+
+        let subtitle_handle = spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.45 }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: format!("{} to control snake, P to pause", controls.name()),
+            font_size: 13.0,
+            color: colors.text,
+            render_order: 5,
+        }));
+
+        let made_with_handle = spawner.spawn(Text::new(Transform::new(
+            TransformVector::Relative(Vector2 { x: 0.5, y: 0.54 }),
+            TransformVector::Absolute(Vector2 { x: 10.0, y: 10.0, }),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), TextConfig {
+            text: "made with wasm-rgame".to_string(),
+            font_size: 19.5,
+            color: colors.text,
+            render_order: 5,
+        }));
+
+        // Join handles that will not be queried for information
+        // for convenience
+        let others = SpawnHandles::new()
+            .with(game_handle)
+            .with(title_handle)
+            .with(subtitle_handle)
+            .with(made_with_handle);
+
+        self.handles = Some(TitleScreenHandles {
+            menu,
+            play_button,
+            time_attack_button,
+            settings_button,
+            _others: others,
+        });
+    }
+
+    fn exit(&mut self, _context: &mut SceneContext) {
+        self.handles = None;
+    }
+
+    fn tick(&mut self, context: &mut SceneContext) -> SceneTransition {
+        let handles = match self.handles {
+            Some(ref mut handles) => handles,
+            None => return SceneTransition::None,
+        };
+
+        let activated = handles.menu.tick(context.key_manager);
+        if activated == Some(handles.play_button) {
+            SceneTransition::Replace(Box::new(RunningScene::new(context.config.rules.mode)))
+        } else if activated == Some(handles.time_attack_button) {
+            SceneTransition::Replace(Box::new(RunningScene::new(GameMode::TimeAttack)))
+        } else if activated == Some(handles.settings_button) {
+            SceneTransition::Replace(Box::new(SettingsScene::new()))
+        } else {
+            SceneTransition::None
+        }
+    }
+
+    fn render(&self, graphics: &mut Graphics, theme: &Theme) {
+        draw_overlay(graphics, theme);
+        if let Some(ref handles) = self.handles {
+            handles.menu.render(graphics, theme);
+        }
+    }
+}
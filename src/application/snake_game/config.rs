@@ -1,11 +1,259 @@
+use serde_json;
+use std::error::Error;
+use std::fmt;
+use toml;
+use wrg_snake_core::{Level, Rules, SetupError, validate_setup};
+use wrg_snake_render::EffectSettings;
 use wrg_2d::{IntVector2, Direction, Grid};
+use super::Controls;
 
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     pub start_position: IntVector2,
     pub start_length: usize,
     pub start_direction: Direction,
 
+    #[serde(skip)]
     pub input_allowed: bool,
+    /// Picked by the player in the settings instead of by the host
+    #[serde(skip)]
+    pub controls: Controls,
 
     pub grid: Grid,
+    pub rules: Rules,
+    pub level: Level,
+
+    pub effects: EffectSettings,
+    pub show_grid_markers: bool,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            start_position: IntVector2 { x: 0, y: 13 },
+            start_length: 3,
+            start_direction: Direction::Right,
+
+            input_allowed: true,
+            controls: Controls::Wasd,
+
+            grid: Grid::new(15, 15),
+            rules: Rules::default(),
+            level: Level::default(),
+
+            effects: EffectSettings::default(),
+            show_grid_markers: true,
+        }
+    }
+}
+
+impl Config {
+    /// Parses a Config from either JSON or TOML, any fields not specified
+    /// are taken from `Config::default()`
+    pub fn parse(source: &str) -> Result<Config, ConfigError> {
+        if source.trim_left().starts_with('{') {
+            Self::from_json(source)
+        } else {
+            Self::from_toml(source)
+        }
+    }
+
+    pub fn from_json(source: &str) -> Result<Config, ConfigError> {
+        let config: Config = serde_json::from_str(source).map_err(ConfigError::Json)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_toml(source: &str) -> Result<Config, ConfigError> {
+        let config: Config = toml::from_str(source).map_err(ConfigError::Toml)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that a game can be started with this Config
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        validate_setup(&self.grid, self.start_position, self.start_length, &self.rules, &self.level)
+            .map_err(ConfigError::Setup)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Json(serde_json::Error),
+    Toml(toml::de::Error),
+    Setup(SetupError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Json(err) => write!(f, "failed to parse JSON: {}", err),
+            ConfigError::Toml(err) => write!(f, "failed to parse TOML: {}", err),
+            ConfigError::Setup(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn description(&self) -> &str {
+        match self {
+            ConfigError::Json(_) => "failed to parse JSON",
+            ConfigError::Toml(_) => "failed to parse TOML",
+            ConfigError::Setup(_) => "the config can't start a game",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wrg_snake_core::{GameMode, LevelError};
+
+    #[test]
+    fn parses_json_and_toml() {
+        let json = Config::parse(r#"{
+            "start_position": { "x": 2, "y": 3 },
+            "start_length": 5,
+            "start_direction": "up",
+            "grid": { "width": 20, "height": 10 }
+        }"#).unwrap();
+
+        let toml = Config::parse(r#"
+            start_length = 5
+            start_direction = "up"
+            start_position = { x = 2, y = 3 }
+
+            [grid]
+            width = 20
+            height = 10
+        "#).unwrap();
+
+        for config in &[json, toml] {
+            assert_eq!(config.start_position, IntVector2 { x: 2, y: 3 });
+            assert_eq!(config.start_length, 5);
+            assert_eq!(config.start_direction, Direction::Up);
+            assert_eq!(config.grid, Grid::new(20, 10));
+            assert!(config.input_allowed);
+            assert_eq!(config.effects, EffectSettings::default());
+        }
+
+        let config = Config::parse(r#"{ "effects": { "screen_shake": false } }"#).unwrap();
+        assert_eq!(config.effects, EffectSettings { enabled: true, screen_shake: false });
+
+        let config = Config::parse("show_grid_markers = false\n[rules]\nspeed = 1.5").unwrap();
+        assert!(!config.show_grid_markers);
+        assert_eq!(config.rules.speed, 1.5);
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let default = Config::default();
+        for source in &["{}", "", r#"{ "start_length": 4 }"#, "start_length = 4"] {
+            let config = Config::parse(source).unwrap();
+            assert_eq!(config.start_position, default.start_position);
+            assert_eq!(config.start_direction, default.start_direction);
+            assert_eq!(config.grid, default.grid);
+            assert_eq!(config.input_allowed, default.input_allowed);
+        }
+    }
+
+    #[test]
+    fn reports_invalid_configs() {
+        match Config::parse(r#"{ "start_direction": "sideways" }"#) {
+            Err(ConfigError::Json(_)) => (),
+            _ => panic!("expected a JSON error"),
+        }
+
+        match Config::parse("start_lenght = 3") {
+            Err(ConfigError::Toml(_)) => (),
+            _ => panic!("expected a TOML error"),
+        }
+
+        match Config::parse(r#"{ "start_position": { "x": 0, "y": 15 } }"#) {
+            Err(ConfigError::Setup(SetupError::StartPositionOffGrid { start_position, .. })) => {
+                assert_eq!(start_position, IntVector2 { x: 0, y: 15 });
+            },
+            _ => panic!("expected StartPositionOffGrid"),
+        }
+
+        match Config::parse(r#"{ "start_length": 10, "grid": { "width": 3, "height": 3 }, "start_position": { "x": 0, "y": 0 } }"#) {
+            Err(ConfigError::Setup(SetupError::StartLengthExceedsGrid { start_length: 10, grid_area: 9 })) => (),
+            _ => panic!("expected StartLengthExceedsGrid"),
+        }
+
+        match Config::parse(r#"{ "level": { "portals": [{ "a": { "x": 1, "y": 1 }, "b": { "x": 1, "y": 15 } }] } }"#) {
+            Err(ConfigError::Setup(SetupError::InvalidLevel(LevelError::PortalOffGrid { .. }))) => (),
+            _ => panic!("expected InvalidLevel"),
+        }
+
+        match Config::parse(r#"{ "level": { "portals": [{ "a": { "x": 0, "y": 13 }, "b": { "x": 5, "y": 5 } }] } }"#) {
+            Err(ConfigError::Setup(SetupError::StartPositionOnPortal { .. })) => (),
+            _ => panic!("expected StartPositionOnPortal"),
+        }
+
+        match Config::parse(r#"{ "level": { "hazards": [{ "kind": "spikes", "cells": [{ "x": 0, "y": 13 }], "on": 1, "off": 1 }] } }"#) {
+            Err(ConfigError::Setup(SetupError::StartPositionOnHazard { .. })) => (),
+            _ => panic!("expected StartPositionOnHazard"),
+        }
+
+        let config = Config::parse(r#"{ "level": { "portals": [{ "a": { "x": 1, "y": 1 }, "b": { "x": 5, "y": 5 } }] } }"#).unwrap();
+        assert_eq!(config.level.portal_exit(IntVector2 { x: 5, y: 5 }), Some(IntVector2 { x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn validate_catches_insane_configs() {
+        assert!(Config::default().validate().is_ok());
+
+        let mut config = Config::default();
+        config.grid = Grid::new(0, 15);
+        match config.validate() {
+            Err(ConfigError::Setup(SetupError::ZeroSizedGrid { .. })) => (),
+            _ => panic!("expected ZeroSizedGrid"),
+        }
+
+        let mut config = Config::default();
+        config.start_position = IntVector2 { x: -1, y: 0 };
+        match config.validate() {
+            Err(ConfigError::Setup(SetupError::StartPositionOffGrid { .. })) => (),
+            _ => panic!("expected StartPositionOffGrid"),
+        }
+
+        let mut config = Config::default();
+        config.start_length = 0;
+        match config.validate() {
+            Err(ConfigError::Setup(SetupError::ZeroStartLength)) => (),
+            _ => panic!("expected ZeroStartLength"),
+        }
+
+        let mut config = Config::default();
+        config.rules.speed = 0.0;
+        match config.validate() {
+            Err(ConfigError::Setup(SetupError::InvalidSpeed { .. })) => (),
+            _ => panic!("expected InvalidSpeed"),
+        }
+
+        // the time limit is only checked in time attack
+        let mut config = Config::default();
+        config.rules.time_attack.duration = 0.0;
+        assert!(config.validate().is_ok());
+        config.rules.mode = GameMode::TimeAttack;
+        match config.validate() {
+            Err(ConfigError::Setup(SetupError::InvalidTimeLimit { .. })) => (),
+            _ => panic!("expected InvalidTimeLimit"),
+        }
+
+        // large grids allow correspondingly long snakes
+        let mut config = Config::default();
+        config.grid = Grid::new(100, 100);
+        config.start_position = IntVector2 { x: 0, y: 0 };
+        config.start_length = 5000;
+        assert!(config.validate().is_ok());
+
+        config.start_length = 10001;
+        match config.validate() {
+            Err(ConfigError::Setup(SetupError::StartLengthExceedsGrid { start_length: 10001, grid_area: 10000 })) => (),
+            _ => panic!("expected StartLengthExceedsGrid"),
+        }
+    }
 }
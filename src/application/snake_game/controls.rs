@@ -0,0 +1,55 @@
+use wasm_rgame::{KeyManager, key_codes};
+use wrg_2d::Direction;
+
+/// Which keys steer the snake
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Controls {
+    Wasd,
+    Arrows,
+    /// Either WASD or the arrow keys
+    Both,
+}
+
+/// Keys for up, right, down and left, in the order they're checked
+const WASD_KEYS : [(u8, Direction); 4] = [
+    (key_codes::W, Direction::Up),
+    (key_codes::D, Direction::Right),
+    (key_codes::S, Direction::Down),
+    (key_codes::A, Direction::Left),
+];
+
+const ARROW_KEYS : [(u8, Direction); 4] = [
+    (key_codes::UP_ARROW, Direction::Up),
+    (key_codes::RIGHT_ARROW, Direction::Right),
+    (key_codes::DOWN_ARROW, Direction::Down),
+    (key_codes::LEFT_ARROW, Direction::Left),
+];
+
+impl Controls {
+    pub const ALL: [Controls; 3] = [Controls::Wasd, Controls::Arrows, Controls::Both];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Controls::Wasd => "WASD",
+            Controls::Arrows => "Arrows",
+            Controls::Both => "WASD + Arrows",
+        }
+    }
+
+    /// The controls after these, wrapping around to the first
+    pub fn next(&self) -> Controls {
+        let index = Self::ALL.iter().position(|controls| controls == self).expect("ALL contains every Controls");
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// The direction of the first steering key held down, if any
+    pub fn wanted_direction(&self, key_manager: &KeyManager) -> Option<Direction> {
+        let wasd: &[(u8, Direction)] = if *self == Controls::Arrows { &[] } else { &WASD_KEYS };
+        let arrows: &[(u8, Direction)] = if *self == Controls::Wasd { &[] } else { &ARROW_KEYS };
+
+        wasd.iter().chain(arrows)
+            .find(|&&(key, _)| key_manager.key_down(key))
+            .map(|&(_, direction)| direction)
+    }
+}
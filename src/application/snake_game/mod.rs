@@ -1,104 +1,128 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use wasm_rgame::Canvas;
 use wasm_rgame::delegate_prelude::*;
 use wbg_rand::{Rng, wasm_rng};
-use wrg_2d::{Grid, IntVector2};
+use wrg_2d::Direction;
+use wrg_snake_core::{Game, GameEvent, GameStats, RandomSource};
+use wrg_snake_render::{RenderState, RenderTarget, SnakeGameRenderer, ThemeHandle};
 
 mod config;
-mod food;
-mod renderer;
-mod snake;
+mod controls;
 
-pub use self::config::Config;
-
-use self::food::Food;
-use self::renderer::SnakeGameRenderer;
-use self::snake::{PlayerSnakeController, Snake, SnakeActionMoveTo};
-
-pub use self::renderer::calculate_grid_canvas_size;
-
-// Because this is a quick demo project, the goal is to get to
-// interesting gameplay fast. Therefore the ramp-up time is
-// fairly short :).
-const ACTION_INTERVAL_BASE : f64 = 0.13;
-const ACTION_INTERVAL_MIN : f64 = 0.05;
-
-const ACTION_INTERVAL_DECR_PERCENT : f64 = 0.8;
+pub use self::config::{Config, ConfigError};
+pub use self::controls::Controls;
+pub use wrg_snake_core::{GameMode, GameState};
 
 pub struct SnakeGame {
     handle: SnakeGameHandle,
-    grid: Grid,
-    snake: Snake,
-    foods: Vec<Food>,
-    snake_controller: PlayerSnakeController,
+    game: Game,
+    controls: Controls,
     renderer: SnakeGameRenderer,
-    input_allowed: bool,
+    events: Vec<GameEvent>,
 
-    /// Last interval this SnakeGame saw
-    last_interval: f64,
-    interval_time: f64,
+    /// Seconds the game has been running for, which doesn't advance while paused
+    time: f64,
+    last_total_s: Option<f64>,
 }
 
 #[derive(Clone)]
 pub struct SnakeGameHandle {
-    state: Rc<RefCell<SnakeGameState>>,
+    state: Rc<RefCell<GameState>>,
+    size: Rc<Cell<usize>>,
+    stats: Rc<RefCell<GameStats>>,
+    time_left: Rc<Cell<Option<f64>>>,
+    paused: Rc<Cell<bool>>,
+    time: Rc<Cell<f64>>,
+}
+
+/// Lets the renderer draw into `Graphics`
+struct GraphicsTarget<'a> {
+    graphics: &'a mut Graphics,
+}
+
+impl<'a> RenderTarget for GraphicsTarget<'a> {
+    fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: [u8; 4]) {
+        self.graphics.draw_rect(x, y, width, height, color, 0.0);
+    }
 }
 
-#[derive(Clone, Copy)]
-pub enum SnakeGameState {
-    Running,
-    Finished {
-        size: usize,
-        time: f64,
-    },
+struct WasmRandom;
+
+impl RandomSource for WasmRandom {
+    fn gen_range(&mut self, low: usize, high: usize) -> usize {
+        wasm_rng().gen_range(low, high)
+    }
 }
 
 impl SnakeGame {
-    pub fn new(config: Config) -> SnakeGame {
+    pub fn new(config: Config, theme: ThemeHandle) -> Result<SnakeGame, ConfigError> {
+        config.validate()?;
+
         let Config {
-            start_position, start_length, start_direction, input_allowed, grid,
+            start_position, start_length, start_direction, input_allowed, controls,
+            grid, rules, level, effects, show_grid_markers,
         } = config;
+        let game = Game::new(grid, start_position, start_length, start_direction, input_allowed)
+            .expect("start_length is validated against the grid area")
+            .with_rules(rules)
+            .with_level(level)
+            .expect("the level is validated with the config");
+
+        Ok(SnakeGame {
+            handle: SnakeGameHandle {
+                state: Rc::new(RefCell::new(GameState::Running)),
+                size: Rc::new(Cell::new(game.snake().length())),
+                stats: Rc::new(RefCell::new(GameStats::default())),
+                time_left: Rc::new(Cell::new(game.time_left())),
+                paused: Rc::new(Cell::new(false)),
+                time: Rc::new(Cell::new(0.0)),
+            },
+            game,
+            controls,
+            renderer: SnakeGameRenderer::new(theme, effects).with_grid_markers(show_grid_markers),
+            events: Vec::new(),
+            time: 0.0,
+            last_total_s: None,
+        })
+    }
 
-        SnakeGame {
-            handle: SnakeGameHandle { state: Rc::new(RefCell::new(SnakeGameState::Running)) },
-            grid,
-            snake: Snake::new(start_position, start_length),
-            foods: Vec::new(),
-            snake_controller: PlayerSnakeController::new(start_direction),
-            renderer: SnakeGameRenderer::new(),
-            input_allowed,
-            last_interval: 0.0,
-            interval_time: ACTION_INTERVAL_BASE,
+    /// Runs the game up to `total_s` seconds since the application started,
+    /// only time spent unpaused counts towards the game
+    pub fn advance<R: RandomSource>(&mut self, total_s: f64, wanted_direction: Option<Direction>, random: &mut R) {
+        let paused = self.handle.paused.get();
+        if let Some(last_total_s) = self.last_total_s {
+            if !paused {
+                self.time += total_s - last_total_s;
+            }
         }
-    }
+        self.last_total_s = Some(total_s);
 
-    fn generate_foods(&mut self) {
-        if !self.foods.is_empty() {
+        if paused {
             return;
         }
 
-        loop {
-            let pos = self.random_grid_position();
-
-            let position_already_taken = {
-                let mut collidable_positions = self.snake.positions()
-                    .chain(self.foods.iter().map(|f| f.pos()));
-                collidable_positions.any(|p| *p == pos)
-            };
-
-            if position_already_taken {
-                continue;
-            }
+        if let Some(direction) = wanted_direction {
+            self.game.store_direction_change(direction);
+        }
 
-            self.foods.push(Food::new(pos));
-            break;
+        let state = self.game.tick(self.time, random, &mut self.events);
+        for event in self.events.drain(..) {
+            self.renderer.handle_event(&event, self.time);
         }
+
+        self.renderer.update(self.time);
+        *(self.handle.state.borrow_mut()) = state;
+        self.handle.size.set(self.game.snake().length());
+        self.handle.stats.borrow_mut().clone_from(self.game.stats());
+        self.handle.time_left.set(self.game.time_left());
+        self.handle.time.set(self.time);
     }
 
-    fn random_grid_position(&self) -> IntVector2 {
-        let x = wasm_rng().gen_range(0, self.grid.width as usize) as i32;
-        let y = wasm_rng().gen_range(0, self.grid.height as usize) as i32;
-        IntVector2 { x, y }
+    /// How many times the snake has moved
+    #[cfg(test)]
+    pub fn moves(&self) -> usize {
+        self.game.moves().len()
     }
 }
 
@@ -110,63 +134,17 @@ impl Delegate for SnakeGame {
         _mouse_state: &MouseState,
         _delegate_spawner: &mut DelegateSpawner,
     ) {
-        let new_state = match self.handle.state.replace(SnakeGameState::Running) {
-            SnakeGameState::Running => {
-                self.generate_foods();
-
-                self.snake_controller.store_direction_change(key_manager);
-
-                let mut state;
-                let diff = context.total_s() - self.last_interval;
-
-                // Instead of making up for lost intervals, let's just do actions if
-                // at least one interval has passed. We don't want to jump intervals
-                // even if the game is laggy. This is exploitable, but that's okay.
-                if diff > self.interval_time {
-                    self.last_interval = context.total_s();
-
-                    if self.input_allowed {
-                        // Only change the direction once per interval
-                        self.snake_controller.change_direction();
-                    }
-
-                    let SnakeActionMoveTo { position } = self.snake_controller.action(&self.snake);
-                    // If the snake goes off the grid, we wrap it around
-                    let position = self.grid.wrap(position);
-
-                    let prev_food_len = self.foods.len();
-                    // remove all foods that collide with the new position
-                    self.foods.retain(|food| *food.pos() != position);
-                    let eaten_count = prev_food_len.saturating_sub(self.foods.len());
-                    for _ in 0..eaten_count {
-                        // decrease interval time each time snake grows
-                        self.interval_time = (self.interval_time * ACTION_INTERVAL_DECR_PERCENT).max(ACTION_INTERVAL_MIN);
-                        self.snake.grow();
-                    }
-
-                    let collided_with_self = self.snake.positions().any(|p| *p == position);
-                    if collided_with_self {
-                        state = SnakeGameState::Finished {
-                            size: self.snake.length(),
-                            time: context.total_s(),
-                        };
-                    } else {
-                        self.snake.move_to(position);
-                        state = SnakeGameState::Running;
-                    }
-                } else {
-                    state = SnakeGameState::Running
-                }
-
-                state
-            },
-            finished => finished,
-        };
-        *(self.handle.state.borrow_mut()) = new_state;
+        let wanted_direction = self.controls.wanted_direction(key_manager);
+        self.advance(context.total_s(), wanted_direction, &mut WasmRandom);
     }
 
     fn render(&self, graphics: &mut Graphics) {
-        self.renderer.render(&self.grid, &self.snake, &self.foods, graphics);
+        let canvas = Canvas::instance();
+        self.renderer.render(
+            &mut GraphicsTarget { graphics },
+            (canvas.width() as f32, canvas.height() as f32),
+            &RenderState::of(&self.game),
+        );
     }
 
     // render behind things
@@ -182,7 +160,33 @@ impl SpawnableDelegate for SnakeGame {
 }
 
 impl SnakeGameHandle {
-    pub fn state(&self) -> SnakeGameState {
+    /// The state of the game, `Finished::time` is in game time like `time()`
+    pub fn state(&self) -> GameState {
         *self.state.borrow()
     }
+
+    /// The length of the snake
+    pub fn size(&self) -> usize {
+        self.size.get()
+    }
+
+    /// What happened in the game so far
+    pub fn stats(&self) -> GameStats {
+        self.stats.borrow().clone()
+    }
+
+    /// Seconds left in time attack, `None` in other modes
+    pub fn time_left(&self) -> Option<f64> {
+        self.time_left.get()
+    }
+
+    /// Seconds the game has been running for, not counting time spent paused
+    pub fn time(&self) -> f64 {
+        self.time.get()
+    }
+
+    /// Stops the game and its effects until unpaused
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.set(paused);
+    }
 }
@@ -1,15 +1,11 @@
-use super::TEXT_COLOR;
+use wrg_snake_render::Theme;
 use wasm_rgame_ui::{ButtonConfig, Button, TextConfig, Transform};
 
-const HOVERED_COLOR: [u8; 4] = [187, 173, 158, 255];
-const CLICKED_COLOR: [u8; 4] = [175, 161, 146, 255];
-const COLOR: [u8; 4] = [190, 176, 161, 255];
-
-pub fn new_button(transform: Transform, text: String, font_size: f32, render_order: i32) -> Button {
+pub fn new_button(theme: &Theme, transform: Transform, text: String, font_size: f32, render_order: i32) -> Button {
     let mut button = Button::new(transform, ButtonConfig {
-        hovered_color: HOVERED_COLOR,
-        clicked_color: CLICKED_COLOR,
-        color: COLOR,
+        hovered_color: theme.button_hovered,
+        clicked_color: theme.button_clicked,
+        color: theme.button,
 
         render_order,
     });
@@ -17,7 +13,7 @@ pub fn new_button(transform: Transform, text: String, font_size: f32, render_ord
     button.set_text(TextConfig {
         text,
         font_size,
-        color: TEXT_COLOR,
+        color: theme.text,
 
         render_order: render_order + 1,
     });
@@ -0,0 +1,27 @@
+use wasm_rgame::KeyManager;
+
+/// Turns keys being held down into single presses, so holding a
+/// key doesn't trigger the same action every tick
+pub struct KeyPress {
+    keys: &'static [u8],
+    was_down: bool,
+}
+
+impl KeyPress {
+    /// Any of `keys` counts as the press. Keys already held down when this
+    /// is created only count once they're released and pressed again.
+    pub fn new(keys: &'static [u8]) -> KeyPress {
+        KeyPress {
+            keys,
+            was_down: true,
+        }
+    }
+
+    /// Whether one of the keys went down since the last call, should be called every tick
+    pub fn pressed(&mut self, key_manager: &KeyManager) -> bool {
+        let down = self.keys.iter().any(|&key| key_manager.key_down(key));
+        let pressed = down && !self.was_down;
+        self.was_down = down;
+        pressed
+    }
+}
@@ -0,0 +1,118 @@
+use wasm_rgame::{Canvas, key_codes};
+use wasm_rgame::delegate_prelude::*;
+use wasm_rgame_ui::{ButtonHandle, Transform, TransformVector, Vector2};
+use wrg_snake_render::Theme;
+use super::{KeyPress, new_button};
+
+const PREVIOUS_KEYS : &[u8] = &[key_codes::W, key_codes::A, key_codes::UP_ARROW, key_codes::LEFT_ARROW];
+const NEXT_KEYS : &[u8] = &[key_codes::S, key_codes::D, key_codes::DOWN_ARROW, key_codes::RIGHT_ARROW];
+const ACTIVATE_KEYS : &[u8] = &[key_codes::ENTER, key_codes::SPACE];
+
+/// Width of the outline drawn around the focused button, in pixels
+const FOCUS_OUTLINE : f32 = 3.0;
+
+/// Buttons that can be used with the mouse or the keyboard, the keyboard moves
+/// the focus between buttons in the order they were added
+pub struct Menu {
+    buttons: Vec<MenuButton>,
+    focused: usize,
+    previous_key: KeyPress,
+    next_key: KeyPress,
+    activate_key: KeyPress,
+}
+
+struct MenuButton {
+    handle: SpawnHandle<ButtonHandle>,
+    /// Center of the button, relative to the canvas
+    position: Vector2,
+    /// Size of the button in pixels
+    size: Vector2,
+}
+
+impl Menu {
+    pub fn new() -> Menu {
+        Menu {
+            buttons: Vec::new(),
+            focused: 0,
+            previous_key: KeyPress::new(PREVIOUS_KEYS),
+            next_key: KeyPress::new(NEXT_KEYS),
+            activate_key: KeyPress::new(ACTIVATE_KEYS),
+        }
+    }
+
+    /// Spawns a button centered at `position` (relative to the canvas) with a `size`
+    /// in pixels, returns the index `tick()` returns when the button is activated
+    pub fn add_button(
+        &mut self,
+        spawner: &mut DelegateSpawner,
+        theme: &Theme,
+        position: Vector2,
+        size: Vector2,
+        text: String,
+        font_size: f32,
+    ) -> usize
+    {
+        let handle = spawner.spawn(new_button(theme, Transform::new(
+            TransformVector::Relative(position),
+            TransformVector::Absolute(size),
+            Vector2 { x: 0.5, y: 0.5, },
+        ), text, font_size, 1));
+
+        self.buttons.push(MenuButton { handle, position, size });
+        self.buttons.len() - 1
+    }
+
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    pub fn focus(&mut self, index: usize) {
+        if index < self.buttons.len() {
+            self.focused = index;
+        }
+    }
+
+    /// Moves the focus, returns the index of the button that was clicked
+    /// or activated from the keyboard this tick
+    pub fn tick(&mut self, key_manager: &KeyManager) -> Option<usize> {
+        let count = self.buttons.len();
+        if count == 0 {
+            return None;
+        }
+
+        if self.previous_key.pressed(key_manager) {
+            self.focused = (self.focused + count - 1) % count;
+        }
+        if self.next_key.pressed(key_manager) {
+            self.focused = (self.focused + 1) % count;
+        }
+        if self.activate_key.pressed(key_manager) {
+            return Some(self.focused);
+        }
+
+        self.buttons.iter().position(|button| button.handle.clicked())
+    }
+
+    /// Outlines the focused button, buttons are drawn on top of
+    /// this so only the outline is visible
+    pub fn render(&self, graphics: &mut Graphics, theme: &Theme) {
+        let button = match self.buttons.get(self.focused) {
+            Some(button) => button,
+            None => return,
+        };
+
+        let canvas = Canvas::instance();
+        let center_x = button.position.x * canvas.width() as f32;
+        let center_y = button.position.y * canvas.height() as f32;
+        let width = button.size.x + FOCUS_OUTLINE * 2.0;
+        let height = button.size.y + FOCUS_OUTLINE * 2.0;
+        graphics.draw_rect(
+            center_x - width / 2.0,
+            center_y - height / 2.0,
+            width,
+            height,
+            theme.button_focused,
+            0.0,
+        );
+    }
+}
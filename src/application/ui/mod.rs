@@ -1,4 +1,6 @@
-pub const TEXT_COLOR: [u8; 4] = [66, 75, 84, 255];
-
 mod button;
+mod key_press;
+mod menu;
 pub use self::button::{new_button};
+pub use self::key_press::KeyPress;
+pub use self::menu::Menu;
@@ -1,11 +1,16 @@
 #![feature(proc_macro, extern_prelude, wasm_custom_section, wasm_import_module)]
 
-extern crate circular_queue;
+#[macro_use]
+extern crate serde;
+extern crate serde_json;
+extern crate toml;
 extern crate wasm_bindgen;
 extern crate wasm_rgame_ui;
 extern crate wasm_rgame;
 extern crate wbg_rand;
 extern crate wrg_2d;
+extern crate wrg_snake_core;
+extern crate wrg_snake_render;
 
 use wasm_bindgen::prelude::*;
 
@@ -22,9 +27,25 @@ pub struct wrg_snake_EntryPoint {}
 #[wasm_bindgen]
 impl wrg_snake_EntryPoint {
     pub fn init(application: &mut Application) {
-        let spawner = application.as_spawner();
+        Self::init_with(application, Config::default());
+    }
+
+    /// Initializes the game with a `Config` passed from the host as a JSON or TOML
+    /// string. Any fields left out fall back to the defaults used by `init`.
+    ///
+    /// Returns an error describing the problem if the config is malformed or invalid.
+    pub fn init_with_config(application: &mut Application, config: &str) -> Result<(), JsValue> {
+        let config = Config::parse(config)
+            .map_err(|err| JsValue::from_str(&format!("Invalid config: {}", err)))?;
+
+        Self::init_with(application, config);
+        Ok(())
+    }
+}
 
-        let app_delegate = ApplicationDelegate::new(spawner);
-        spawner.spawn_root(app_delegate);
+impl wrg_snake_EntryPoint {
+    fn init_with(application: &mut Application, config: Config) {
+        let app_delegate = ApplicationDelegate::new(config, Box::new(LocalStorage));
+        application.as_spawner().spawn_root(app_delegate);
     }
 }
@@ -2,6 +2,8 @@ use vector::IntVector2;
 
 /// Cardinal Direction enum for 2D
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[repr(u8)]
 pub enum Direction {
     Up = 0,
@@ -11,6 +13,14 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// All directions in clockwise order, starting from `Up`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
     pub fn to_position(&self) -> IntVector2 {
         match self {
             Direction::Up => IntVector2 { x: 0, y: 1 },
@@ -20,6 +30,18 @@ impl Direction {
         }
     }
 
+    /// Returns the direction matching a unit delta, or None if `delta`
+    /// isn't exactly one orthogonal step
+    pub fn from_delta(delta: IntVector2) -> Option<Direction> {
+        match (delta.x, delta.y) {
+            (0, 1) => Some(Direction::Up),
+            (1, 0) => Some(Direction::Right),
+            (0, -1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
     pub fn opposite(&self) -> Direction {
         match self {
             Direction::Up => Direction::Down,
@@ -28,4 +50,56 @@ impl Direction {
             Direction::Down => Direction::Up,
         }
     }
+
+    pub fn rotate_cw(&self) -> Direction {
+        Self::ALL[(*self as usize + 1) % 4]
+    }
+
+    pub fn rotate_ccw(&self) -> Direction {
+        Self::ALL[(*self as usize + 3) % 4]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations_work_as_expected() {
+        assert_eq!(Direction::Up.rotate_cw(), Direction::Right);
+        assert_eq!(Direction::Right.rotate_cw(), Direction::Down);
+        assert_eq!(Direction::Down.rotate_cw(), Direction::Left);
+        assert_eq!(Direction::Left.rotate_cw(), Direction::Up);
+
+        for direction in Direction::ALL.iter() {
+            assert_eq!(direction.rotate_cw().rotate_ccw(), *direction);
+            assert_eq!(direction.rotate_cw().rotate_cw(), direction.opposite());
+            assert_eq!(direction.rotate_ccw().rotate_ccw(), direction.opposite());
+        }
+    }
+
+    #[test]
+    fn from_delta_round_trips() {
+        for direction in Direction::ALL.iter() {
+            assert_eq!(Direction::from_delta(direction.to_position()), Some(*direction));
+            assert_eq!(-direction.to_position(), direction.opposite().to_position());
+        }
+
+        assert_eq!(Direction::from_delta(IntVector2 { x: 0, y: 0 }), None);
+        assert_eq!(Direction::from_delta(IntVector2 { x: 1, y: 1 }), None);
+        assert_eq!(Direction::from_delta(IntVector2 { x: 0, y: 2 }), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        let expected = ["\"up\"", "\"right\"", "\"down\"", "\"left\""];
+        for (direction, expected) in Direction::ALL.iter().zip(expected.iter()) {
+            let json = ::serde_json::to_string(direction).unwrap();
+            assert_eq!(json, *expected);
+            assert_eq!(::serde_json::from_str::<Direction>(&json).unwrap(), *direction);
+        }
+
+        assert!(::serde_json::from_str::<Direction>("\"Up\"").is_err());
+    }
 }
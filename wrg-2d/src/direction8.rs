@@ -0,0 +1,139 @@
+use direction::Direction;
+use vector::IntVector2;
+
+/// Cardinal and diagonal Direction enum for 2D, for when movement
+/// isn't restricted to orthogonal steps
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[repr(u8)]
+pub enum Direction8 {
+    Up = 0,
+    UpRight = 1,
+    Right = 2,
+    DownRight = 3,
+    Down = 4,
+    DownLeft = 5,
+    Left = 6,
+    UpLeft = 7,
+}
+
+impl Direction8 {
+    /// All directions in clockwise order, starting from `Up`
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn to_position(&self) -> IntVector2 {
+        match self {
+            Direction8::Up => IntVector2 { x: 0, y: 1 },
+            Direction8::UpRight => IntVector2 { x: 1, y: 1 },
+            Direction8::Right => IntVector2 { x: 1, y: 0 },
+            Direction8::DownRight => IntVector2 { x: 1, y: -1 },
+            Direction8::Down => IntVector2 { x: 0, y: -1 },
+            Direction8::DownLeft => IntVector2 { x: -1, y: -1 },
+            Direction8::Left => IntVector2 { x: -1, y: 0 },
+            Direction8::UpLeft => IntVector2 { x: -1, y: 1 },
+        }
+    }
+
+    /// Returns the direction matching a single step (orthogonal or diagonal),
+    /// or None if `delta` isn't exactly one step
+    pub fn from_delta(delta: IntVector2) -> Option<Direction8> {
+        Self::ALL.iter().cloned().find(|direction| direction.to_position() == delta)
+    }
+
+    pub fn opposite(&self) -> Direction8 {
+        Self::ALL[(*self as usize + 4) % 8]
+    }
+
+    /// Rotates by 45 degrees clockwise
+    pub fn rotate_cw(&self) -> Direction8 {
+        Self::ALL[(*self as usize + 1) % 8]
+    }
+
+    /// Rotates by 45 degrees counter-clockwise
+    pub fn rotate_ccw(&self) -> Direction8 {
+        Self::ALL[(*self as usize + 7) % 8]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        (*self as u8) % 2 == 1
+    }
+
+    /// Returns the cardinal Direction, or None if this direction is diagonal
+    pub fn to_cardinal(&self) -> Option<Direction> {
+        Direction::from_delta(self.to_position())
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotations_work_as_expected() {
+        assert_eq!(Direction8::Up.rotate_cw(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.rotate_ccw(), Direction8::UpLeft);
+        assert_eq!(Direction8::UpLeft.rotate_cw(), Direction8::Up);
+
+        for direction in Direction8::ALL.iter() {
+            assert_eq!(direction.rotate_cw().rotate_ccw(), *direction);
+            assert_eq!(direction.opposite().opposite(), *direction);
+            assert_eq!(-direction.to_position(), direction.opposite().to_position());
+        }
+    }
+
+    #[test]
+    fn from_delta_round_trips() {
+        for direction in Direction8::ALL.iter() {
+            assert_eq!(Direction8::from_delta(direction.to_position()), Some(*direction));
+        }
+
+        assert_eq!(Direction8::from_delta(IntVector2 { x: 0, y: 0 }), None);
+        assert_eq!(Direction8::from_delta(IntVector2 { x: 2, y: 1 }), None);
+    }
+
+    #[test]
+    fn converts_to_and_from_cardinal() {
+        for direction in Direction::ALL.iter() {
+            let direction8 = Direction8::from(*direction);
+            assert!(!direction8.is_diagonal());
+            assert_eq!(direction8.to_cardinal(), Some(*direction));
+            assert_eq!(direction8.to_position(), direction.to_position());
+        }
+
+        assert!(Direction8::DownLeft.is_diagonal());
+        assert_eq!(Direction8::DownLeft.to_cardinal(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        for direction in Direction8::ALL.iter() {
+            let json = ::serde_json::to_string(direction).unwrap();
+            assert_eq!(::serde_json::from_str::<Direction8>(&json).unwrap(), *direction);
+        }
+
+        assert_eq!(::serde_json::to_string(&Direction8::UpRight).unwrap(), "\"up_right\"");
+        assert_eq!(::serde_json::to_string(&Direction8::Left).unwrap(), "\"left\"");
+    }
+}
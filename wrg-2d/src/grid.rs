@@ -1,6 +1,7 @@
 use vector::IntVector2;
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Grid {
     pub width: u16,
     pub height: u16,
@@ -11,6 +12,11 @@ impl Grid {
         Grid { width, height, }
     }
 
+    /// Number of cells in the grid
+    pub fn area(&self) -> usize {
+        (self.width as usize) * (self.height as usize)
+    }
+
     pub fn contains(&self, position: IntVector2) -> bool {
         position.x >= 0 && position.x < (self.width as i32) &&
         position.y >= 0 && position.y < (self.height as i32)
@@ -22,6 +28,27 @@ impl Grid {
             y: position.y.mod_euc(self.height as i32),
         }
     }
+
+    /// Shortest delta from `from` to `to` when the grid wraps around its edges,
+    /// so `from + wrapped_delta(from, to)` wraps to `to`. Ties (exactly half the
+    /// grid away) resolve to the positive direction.
+    pub fn wrapped_delta(&self, from: IntVector2, to: IntVector2) -> IntVector2 {
+        let shortest = |delta: i32, size: i32| {
+            let delta = delta.mod_euc(size);
+            if delta * 2 > size { delta - size } else { delta }
+        };
+
+        let delta = to - from;
+        IntVector2 {
+            x: shortest(delta.x, self.width as i32),
+            y: shortest(delta.y, self.height as i32),
+        }
+    }
+
+    /// Manhattan distance between two positions when the grid wraps around its edges
+    pub fn torus_distance(&self, a: IntVector2, b: IntVector2) -> u32 {
+        IntVector2::ZERO.manhattan_distance(self.wrapped_delta(a, b))
+    }
 }
 
 #[cfg(test)]
@@ -45,6 +72,13 @@ mod tests {
         assert!(!grid.contains(IntVector2 { x: 30, y: 1 }));
     }
 
+    #[test]
+    fn area_does_not_overflow() {
+        assert_eq!(Grid::new(5, 4).area(), 20);
+        assert_eq!(Grid::new(0, 4).area(), 0);
+        assert_eq!(Grid::new(65535, 65535).area(), 65535 * 65535);
+    }
+
     #[test]
     fn wrap_works_as_expected() {
         let grid = Grid::new(5, 5);
@@ -56,4 +90,42 @@ mod tests {
         assert_eq!(grid.wrap(IntVector2 { x: 1, y: 5 }), IntVector2 { x: 1, y: 0 });
         assert_eq!(grid.wrap(IntVector2 { x: -1, y: 8 }), IntVector2 { x: 4, y: 3 });
     }
+
+    #[test]
+    fn wrapped_delta_takes_shortest_path() {
+        let grid = Grid::new(5, 4);
+        assert_eq!(grid.wrapped_delta(IntVector2 { x: 0, y: 0 }, IntVector2 { x: 0, y: 0 }), IntVector2 { x: 0, y: 0 });
+        assert_eq!(grid.wrapped_delta(IntVector2 { x: 0, y: 0 }, IntVector2 { x: 1, y: 1 }), IntVector2 { x: 1, y: 1 });
+        assert_eq!(grid.wrapped_delta(IntVector2 { x: 4, y: 0 }, IntVector2 { x: 0, y: 0 }), IntVector2 { x: 1, y: 0 });
+        assert_eq!(grid.wrapped_delta(IntVector2 { x: 0, y: 0 }, IntVector2 { x: 4, y: 3 }), IntVector2 { x: -1, y: -1 });
+        assert_eq!(grid.wrapped_delta(IntVector2 { x: 1, y: 0 }, IntVector2 { x: 4, y: 0 }), IntVector2 { x: -2, y: 0 });
+        // exactly half way around resolves to positive
+        assert_eq!(grid.wrapped_delta(IntVector2 { x: 0, y: 3 }, IntVector2 { x: 0, y: 1 }), IntVector2 { x: 0, y: 2 });
+
+        for &(from, to) in &[
+            (IntVector2 { x: 3, y: 1 }, IntVector2 { x: 0, y: 3 }),
+            (IntVector2 { x: 0, y: 2 }, IntVector2 { x: 4, y: 0 }),
+        ] {
+            assert_eq!(grid.wrap(from + grid.wrapped_delta(from, to)), to);
+        }
+    }
+
+    #[test]
+    fn torus_distance_works_as_expected() {
+        let grid = Grid::new(5, 5);
+        assert_eq!(grid.torus_distance(IntVector2 { x: 0, y: 0 }, IntVector2 { x: 0, y: 0 }), 0);
+        assert_eq!(grid.torus_distance(IntVector2 { x: 0, y: 0 }, IntVector2 { x: 2, y: 2 }), 4);
+        assert_eq!(grid.torus_distance(IntVector2 { x: 0, y: 0 }, IntVector2 { x: 4, y: 4 }), 2);
+        assert_eq!(grid.torus_distance(IntVector2 { x: 4, y: 1 }, IntVector2 { x: 0, y: 1 }), 1);
+        assert_eq!(grid.torus_distance(IntVector2 { x: 1, y: 0 }, IntVector2 { x: 3, y: 4 }), 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        let grid = Grid::new(15, 20);
+        let json = ::serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, r#"{"width":15,"height":20}"#);
+        assert_eq!(::serde_json::from_str::<Grid>(&json).unwrap(), grid);
+    }
 }
@@ -1,8 +1,16 @@
 #![feature(euclidean_division)]
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
 mod direction;
+mod direction8;
 mod grid;
 mod vector;
 
 pub use vector::IntVector2;
 pub use grid::Grid;
 pub use direction::Direction;
+pub use direction8::Direction8;
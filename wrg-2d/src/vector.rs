@@ -1,11 +1,33 @@
-use std::ops::{Add, Sub};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IntVector2 {
     pub x: i32,
     pub y: i32,
 }
 
+impl IntVector2 {
+    pub const ZERO: IntVector2 = IntVector2 { x: 0, y: 0 };
+
+    pub fn new(x: i32, y: i32) -> IntVector2 {
+        IntVector2 { x, y }
+    }
+
+    /// Number of orthogonal steps needed to get from `self` to `other`
+    pub fn manhattan_distance(&self, other: IntVector2) -> u32 {
+        let delta = other - *self;
+        (delta.x.abs() + delta.y.abs()) as u32
+    }
+
+    /// Number of steps needed to get from `self` to `other` when diagonal
+    /// steps are allowed
+    pub fn chebyshev_distance(&self, other: IntVector2) -> u32 {
+        let delta = other - *self;
+        delta.x.abs().max(delta.y.abs()) as u32
+    }
+}
+
 impl Add for IntVector2 {
     type Output = IntVector2;
 
@@ -14,6 +36,13 @@ impl Add for IntVector2 {
     }
 }
 
+impl AddAssign for IntVector2 {
+    fn add_assign(&mut self, other: IntVector2) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
 impl Sub for IntVector2 {
     type Output = IntVector2;
 
@@ -21,3 +50,70 @@ impl Sub for IntVector2 {
         IntVector2 {x: self.x - other.x, y: self.y - other.y}
     }
 }
+
+impl SubAssign for IntVector2 {
+    fn sub_assign(&mut self, other: IntVector2) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl Neg for IntVector2 {
+    type Output = IntVector2;
+
+    fn neg(self) -> IntVector2 {
+        IntVector2 {x: -self.x, y: -self.y}
+    }
+}
+
+impl Mul<i32> for IntVector2 {
+    type Output = IntVector2;
+
+    fn mul(self, scalar: i32) -> IntVector2 {
+        IntVector2 {x: self.x * scalar, y: self.y * scalar}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_work_as_expected() {
+        let a = IntVector2 { x: 2, y: -3 };
+        let b = IntVector2 { x: -1, y: 5 };
+        assert_eq!(a + b, IntVector2 { x: 1, y: 2 });
+        assert_eq!(a - b, IntVector2 { x: 3, y: -8 });
+        assert_eq!(-a, IntVector2 { x: -2, y: 3 });
+        for &(scalar, product) in &[(3, IntVector2 { x: 6, y: -9 }), (0, IntVector2::ZERO)] {
+            assert_eq!(a * scalar, product);
+        }
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c, IntVector2 { x: 1, y: 2 });
+        c -= b;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn distances_work_as_expected() {
+        let origin = IntVector2::ZERO;
+        assert_eq!(origin.manhattan_distance(origin), 0);
+        assert_eq!(origin.manhattan_distance(IntVector2 { x: 3, y: -4 }), 7);
+        assert_eq!(IntVector2 { x: -2, y: 1 }.manhattan_distance(IntVector2 { x: 2, y: -1 }), 6);
+
+        assert_eq!(origin.chebyshev_distance(origin), 0);
+        assert_eq!(origin.chebyshev_distance(IntVector2 { x: 3, y: -4 }), 4);
+        assert_eq!(IntVector2 { x: -2, y: 1 }.chebyshev_distance(IntVector2 { x: 2, y: -1 }), 4);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        let vector = IntVector2 { x: -3, y: 12 };
+        let json = ::serde_json::to_string(&vector).unwrap();
+        assert_eq!(json, r#"{"x":-3,"y":12}"#);
+        assert_eq!(::serde_json::from_str::<IntVector2>(&json).unwrap(), vector);
+    }
+}
@@ -0,0 +1,25 @@
+use wrg_2d::IntVector2;
+
+pub struct Food {
+    pos: IntVector2,
+    bonus: bool,
+}
+
+impl Food {
+    pub fn new(pos: IntVector2) -> Food {
+        Food { pos, bonus: false }
+    }
+
+    /// Food that's worth extra, like bonus time in time attack
+    pub fn bonus(pos: IntVector2) -> Food {
+        Food { pos, bonus: true }
+    }
+
+    pub fn is_bonus(&self) -> bool {
+        self.bonus
+    }
+
+    pub fn pos(&self) -> &IntVector2 {
+        &self.pos
+    }
+}
@@ -0,0 +1,743 @@
+use std::collections::HashSet;
+use wrg_2d::{Direction, Grid, IntVector2};
+use food::Food;
+use hazard::HazardCell;
+use level::{Level, LevelError};
+use random::RandomSource;
+use rules::{GameMode, Rules};
+use snake::{PlayerSnakeController, Snake, SnakeActionMoveTo, SnakeError};
+use stats::GameStats;
+
+// Because this is a quick demo project, the goal is to get to
+// interesting gameplay fast. Therefore the ramp-up time is
+// fairly short :).
+const ACTION_INTERVAL_BASE : f64 = 0.13;
+const ACTION_INTERVAL_MIN : f64 = 0.05;
+
+const ACTION_INTERVAL_DECR_PERCENT : f64 = 0.8;
+
+/// Things that happen during a game which other parts of the game react to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameEvent {
+    FoodEaten {
+        position: IntVector2,
+    },
+    /// The head bit the snake's own body with `Rules::tail_cutting`, which cut
+    /// off `removed` segments from there to the tail
+    TailCut {
+        position: IntVector2,
+        removed: usize,
+    },
+    Died {
+        /// Where the head was moving to
+        position: IntVector2,
+        cause: DeathCause,
+    },
+}
+
+/// Why a game ended, the grid wraps around so there are no walls to run into
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeathCause {
+    /// The time ran out in time attack, the snake is still alive
+    OutOfTime,
+    /// The head ran into the snake's own body at `position`
+    SelfCollision {
+        position: IntVector2,
+    },
+    /// The snake covers the whole grid, so there's nowhere left to go
+    FilledGrid,
+    /// The snake ran into a hazard at `position`, or the hazard moved onto the snake
+    Hazard {
+        position: IntVector2,
+    },
+}
+
+impl DeathCause {
+    /// The cell the snake died in, if the death happened in a single cell
+    pub fn position(&self) -> Option<IntVector2> {
+        match self {
+            DeathCause::SelfCollision { position } | DeathCause::Hazard { position } => Some(*position),
+            DeathCause::OutOfTime | DeathCause::FilledGrid => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameState {
+    Running,
+    Finished {
+        size: usize,
+        time: f64,
+        cause: DeathCause,
+    },
+}
+
+/// A single game of snake, advanced by calling `tick()` with the current time
+pub struct Game {
+    grid: Grid,
+    snake: Snake,
+    foods: Vec<Food>,
+    snake_controller: PlayerSnakeController,
+    input_allowed: bool,
+    rules: Rules,
+    level: Level,
+    state: GameState,
+    /// The direction the snake moved in each interval, in order
+    moves: Vec<Direction>,
+    /// When each of `moves` happened, in seconds since the first tick
+    move_times: Vec<f64>,
+    stats: GameStats,
+    /// Whether the head has been in each cell, indexed by `y * width + x`
+    visited: Vec<bool>,
+    /// Time of the first tick, which `stats.duration` is counted from
+    start_time: Option<f64>,
+    /// Seconds added to the time limit by bonus food
+    bonus_time: f64,
+
+    /// Last interval this Game saw, in seconds since the first tick like `move_times`
+    /// so replays ticking at `move_times` see exactly the same intervals
+    last_interval: f64,
+    interval_time: f64,
+    /// How far along (0.0 - 1.0) the current interval is, used to
+    /// interpolate the snake between its previous and current positions
+    interval_progress: f32,
+}
+
+impl Game {
+    pub fn new(
+        grid: Grid,
+        start_position: IntVector2,
+        start_length: usize,
+        start_direction: Direction,
+        input_allowed: bool,
+    ) -> Result<Game, SnakeError>
+    {
+        let snake = Snake::new(start_position, start_length, grid.area())?;
+
+        let mut game = Game {
+            grid,
+            snake,
+            foods: Vec::new(),
+            snake_controller: PlayerSnakeController::new(start_direction),
+            input_allowed,
+            rules: Rules::default(),
+            level: Level::default(),
+            state: GameState::Running,
+            moves: Vec::new(),
+            move_times: Vec::new(),
+            stats: GameStats::default(),
+            visited: vec![false; grid.area()],
+            start_time: None,
+            bonus_time: 0.0,
+            last_interval: 0.0,
+            interval_time: ACTION_INTERVAL_BASE,
+            interval_progress: 1.0,
+        };
+        game.visit(start_position);
+        game.stats.max_speed = 1.0 / game.interval_time;
+        Ok(game)
+    }
+
+    /// Plays the game with `rules` instead of the default rules, must be
+    /// called before the first `tick()`
+    pub fn with_rules(mut self, rules: Rules) -> Game {
+        debug_assert!(rules.speed > 0.0);
+        self.rules = rules;
+        self.interval_time = ACTION_INTERVAL_BASE / rules.speed;
+        self.stats.max_speed = 1.0 / self.interval_time;
+        self
+    }
+
+    /// Plays the game on `level` instead of an empty grid, must be called
+    /// before the first `tick()`. Hazards can't move without a valid level.
+    pub fn with_level(mut self, level: Level) -> Result<Game, LevelError> {
+        level.validate(&self.grid)?;
+        self.level = level;
+        Ok(self)
+    }
+
+    /// Asks the snake to turn, ignored if input isn't allowed in this game
+    pub fn store_direction_change(&mut self, direction: Direction) {
+        if self.input_allowed {
+            self.snake_controller.store_direction_change(direction);
+        }
+    }
+
+    /// Advances the game to `now` (in seconds), pushing anything that
+    /// happened along the way into `events`
+    pub fn tick<R: RandomSource>(&mut self, now: f64, random: &mut R, events: &mut Vec<GameEvent>) -> GameState {
+        if let GameState::Running = self.state {
+            let start_time = *self.start_time.get_or_insert(now);
+            self.stats.duration = now - start_time;
+
+            if self.time_left() == Some(0.0) {
+                self.state = GameState::Finished {
+                    size: self.snake.length(),
+                    time: now,
+                    cause: DeathCause::OutOfTime,
+                };
+                self.interval_progress = 1.0;
+                return self.state;
+            }
+
+            self.generate_foods(random);
+
+            let elapsed = self.stats.duration;
+            let diff = elapsed - self.last_interval;
+
+            // Instead of making up for lost intervals, let's just do actions if
+            // at least one interval has passed. We don't want to jump intervals
+            // even if the game is laggy. This is exploitable, but that's okay.
+            if diff > self.interval_time {
+                self.last_interval = elapsed;
+                self.state = self.step(now, events);
+            }
+
+            self.interval_progress = match self.state {
+                GameState::Running => {
+                    ((elapsed - self.last_interval) / self.interval_time).min(1.0) as f32
+                },
+                // the snake didn't move, so there's nothing to interpolate
+                GameState::Finished { .. } => 1.0,
+            };
+        }
+
+        self.state
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn snake(&self) -> &Snake {
+        &self.snake
+    }
+
+    pub fn foods(&self) -> &Vec<Food> {
+        &self.foods
+    }
+
+    /// The direction the snake last moved in
+    pub fn direction(&self) -> Direction {
+        self.snake_controller.direction()
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    /// The cells covered by the level's hazards right now
+    pub fn hazard_cells(&self) -> Vec<HazardCell> {
+        self.level.hazard_cells(&self.grid, self.moves.len())
+    }
+
+    /// Seconds between moves right now
+    pub fn interval(&self) -> f64 {
+        self.interval_time
+    }
+
+    pub fn interval_progress(&self) -> f32 {
+        self.interval_progress
+    }
+
+    pub fn stats(&self) -> &GameStats {
+        &self.stats
+    }
+
+    /// Every direction the snake has moved in so far, used to record replays
+    pub fn moves(&self) -> &[Direction] {
+        &self.moves
+    }
+
+    /// When each of `moves()` happened, in seconds since the first tick
+    pub fn move_times(&self) -> &[f64] {
+        &self.move_times
+    }
+
+    /// Seconds until the game ends in time attack, including any bonus time
+    pub fn time_left(&self) -> Option<f64> {
+        match self.rules.mode {
+            GameMode::Classic | GameMode::Zen => None,
+            GameMode::TimeAttack => {
+                let limit = self.rules.time_attack.duration + self.bonus_time;
+                Some((limit - self.stats.duration).max(0.0))
+            },
+        }
+    }
+
+    /// Moves the snake one cell
+    fn step(&mut self, now: f64, events: &mut Vec<GameEvent>) -> GameState {
+        // Only change the direction once per interval
+        let previous_direction = self.snake_controller.direction();
+        self.snake_controller.change_direction();
+        let direction = self.snake_controller.direction();
+        self.moves.push(direction);
+        self.move_times.push(self.stats.duration);
+        if direction != previous_direction {
+            self.stats.turns += 1;
+        }
+
+        let SnakeActionMoveTo { position } = self.snake_controller.action(&self.snake);
+        // If the snake goes off the grid, we wrap it around
+        let position = self.grid.wrap(position);
+        // entering a portal puts the head on the other end, still heading the same way
+        let position = self.level.portal_exit(position).unwrap_or(position);
+
+        let prev_food_len = self.foods.len();
+        let bonus_eaten = self.foods.iter().filter(|food| *food.pos() == position && food.is_bonus()).count();
+        // remove all foods that collide with the new position
+        self.foods.retain(|food| *food.pos() != position);
+        let eaten_count = prev_food_len.saturating_sub(self.foods.len());
+        self.bonus_time += bonus_eaten as f64 * self.rules.time_attack.bonus_time;
+        let mut at_capacity = false;
+        if eaten_count > 0 {
+            events.push(GameEvent::FoodEaten { position });
+        }
+        for _ in 0..eaten_count {
+            // decrease interval time each time snake grows, zen keeps a constant speed
+            if self.rules.mode != GameMode::Zen {
+                self.interval_time = (self.interval_time * ACTION_INTERVAL_DECR_PERCENT).max(ACTION_INTERVAL_MIN / self.rules.speed);
+            }
+            // a zen snake overlapping itself can get as long as the grid has cells
+            // before filling it, it can't grow any longer than that
+            at_capacity |= self.snake.grow().is_err();
+        }
+        self.stats.foods_eaten += eaten_count;
+        self.stats.max_speed = self.stats.max_speed.max(1.0 / self.interval_time);
+
+        // in zen the snake passes through itself, with tail cutting it bites off the bitten part
+        let bitten_index = self.snake.positions().position(|p| *p == position);
+        let cut_at = match bitten_index {
+            Some(index) if self.rules.tail_cutting && self.rules.mode != GameMode::Zen => Some(index),
+            _ => None,
+        };
+        let collided_with_self = bitten_index.is_some() && cut_at.is_none() && self.rules.mode != GameMode::Zen;
+        // hazards move along with the snake, so they're checked where they are after this move
+        let hazards: Vec<IntVector2> = self.hazard_cells().iter()
+            .filter(|cell| cell.active)
+            .map(|cell| cell.position)
+            .collect();
+        let death_cause = if collided_with_self {
+            Some(DeathCause::SelfCollision { position })
+        } else if hazards.contains(&position) {
+            Some(DeathCause::Hazard { position })
+        } else {
+            None
+        };
+
+        if let Some(cause) = death_cause {
+            return self.die(now, position, cause, events);
+        }
+
+        self.snake.move_to(position);
+        if let Some(index) = cut_at {
+            // the head takes the place of the bitten segment, so everything before it stays
+            let length = self.snake.length();
+            self.snake.truncate_at(index + 1);
+            events.push(GameEvent::TailCut { position, removed: length - self.snake.length() });
+        }
+        self.visit(position);
+
+        // a hazard moving onto the body hits the snake as well
+        let hit = self.snake.positions().find(|p| hazards.contains(p)).cloned();
+        if let Some(hit) = hit {
+            return self.die(now, position, DeathCause::Hazard { position: hit }, events);
+        }
+
+        // no food can be placed anymore, or the snake can't get any longer
+        if at_capacity || self.grid_filled() {
+            return self.die(now, position, DeathCause::FilledGrid, events);
+        }
+
+        GameState::Running
+    }
+
+    /// Ends the game with the head moving to `position`
+    fn die(&self, now: f64, position: IntVector2, cause: DeathCause, events: &mut Vec<GameEvent>) -> GameState {
+        events.push(GameEvent::Died { position, cause });
+        GameState::Finished {
+            size: self.snake.length(),
+            time: now,
+            cause,
+        }
+    }
+
+    fn visit(&mut self, position: IntVector2) {
+        let index = position.y as usize * self.grid.width as usize + position.x as usize;
+        if !self.visited[index] {
+            self.visited[index] = true;
+            self.stats.tiles_visited += 1;
+        }
+    }
+
+    /// Whether the snake takes up every cell that food could be placed in,
+    /// food isn't placed on portals or hazards. The snake can be on a portal
+    /// or an inactive hazard and hazards can overlap, so each cell counts once.
+    fn grid_filled(&self) -> bool {
+        let hazards = self.hazard_cells();
+        let occupied: HashSet<&IntVector2> = self.snake.positions()
+            .chain(self.level.portal_cells())
+            .chain(hazards.iter().map(|cell| &cell.position))
+            .collect();
+        occupied.len() >= self.grid.area()
+    }
+
+    fn generate_foods<R: RandomSource>(&mut self, random: &mut R) {
+        if !self.foods.is_empty() || self.grid_filled() {
+            return;
+        }
+
+        let hazards: Vec<IntVector2> = self.hazard_cells().iter().map(|cell| cell.position).collect();
+
+        loop {
+            let pos = IntVector2 {
+                x: random.gen_range(0, self.grid.width as usize) as i32,
+                y: random.gen_range(0, self.grid.height as usize) as i32,
+            };
+
+            let position_already_taken = {
+                let mut collidable_positions = self.snake.positions()
+                    .chain(self.foods.iter().map(|f| f.pos()))
+                    .chain(self.level.portal_cells())
+                    .chain(hazards.iter());
+                collidable_positions.any(|p| *p == pos)
+            };
+
+            if position_already_taken {
+                continue;
+            }
+
+            // every few foods in time attack is a bonus, counted so replays place the same bonuses
+            let bonus_every = self.rules.time_attack.bonus_food_every;
+            let is_bonus = self.rules.mode == GameMode::TimeAttack
+                && bonus_every > 0
+                && (self.stats.foods_eaten + 1) % bonus_every == 0;
+            self.foods.push(if is_bonus { Food::bonus(pos) } else { Food::new(pos) });
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hazard::Hazard;
+    use level::Portal;
+    use rules::TimeAttackRules;
+
+    /// Walks through the grid cell by cell so food placement is predictable
+    struct SequentialRandom {
+        next: usize,
+    }
+
+    impl RandomSource for SequentialRandom {
+        fn gen_range(&mut self, low: usize, high: usize) -> usize {
+            self.next += 1;
+            low + self.next % (high - low)
+        }
+    }
+
+    fn tick_intervals(game: &mut Game, intervals: usize, events: &mut Vec<GameEvent>) -> GameState {
+        let mut random = SequentialRandom { next: 0 };
+        let mut now = match game.start_time {
+            Some(start_time) => start_time + game.last_interval,
+            None => {
+                // the first tick only starts the clock, the snake moves an interval later
+                game.tick(0.0, &mut random, events);
+                0.0
+            },
+        };
+        for _ in 0..intervals {
+            now += 1.0;
+            game.tick(now, &mut random, events);
+        }
+        game.state()
+    }
+
+    #[test]
+    fn snake_moves_and_wraps_each_interval() {
+        let mut game = Game::new(Grid::new(3, 3), IntVector2 { x: 1, y: 1 }, 1, Direction::Right, true).unwrap();
+        let mut events = Vec::new();
+
+        tick_intervals(&mut game, 1, &mut events);
+        assert_eq!(game.snake().current_position(), IntVector2 { x: 2, y: 1 });
+        tick_intervals(&mut game, 1, &mut events);
+        assert_eq!(game.snake().current_position(), IntVector2 { x: 0, y: 1 });
+    }
+
+    #[test]
+    fn input_is_ignored_when_not_allowed() {
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 1, y: 1 }, 1, Direction::Right, false).unwrap();
+        game.store_direction_change(Direction::Up);
+        tick_intervals(&mut game, 1, &mut Vec::new());
+        assert_eq!(game.direction(), Direction::Right);
+    }
+
+    #[test]
+    fn running_into_itself_finishes_the_game() {
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 1, y: 1 }, 5, Direction::Right, true).unwrap();
+        let mut events = Vec::new();
+        tick_intervals(&mut game, 4, &mut events);
+
+        for &direction in &[Direction::Up, Direction::Left, Direction::Down] {
+            game.store_direction_change(direction);
+            tick_intervals(&mut game, 1, &mut events);
+        }
+
+        let cause = DeathCause::SelfCollision { position: IntVector2 { x: 4, y: 1 } };
+        match game.state() {
+            GameState::Finished { size, cause: finished_cause, .. } => {
+                assert_eq!(size, 5);
+                assert_eq!(finished_cause, cause);
+            },
+            GameState::Running => panic!("Expected the game to be finished"),
+        }
+        assert_eq!(events.last(), Some(&GameEvent::Died { position: IntVector2 { x: 4, y: 1 }, cause }));
+
+        let stats = game.stats();
+        assert_eq!(stats.turns, 3);
+        // the start cell and the 6 cells moved into before dying
+        assert_eq!(stats.tiles_visited, 7);
+        // a second for each of the 7 moves since the clock started
+        assert_eq!(stats.duration, 7.0);
+    }
+
+    #[test]
+    fn filling_the_grid_finishes_the_game() {
+        let mut game = Game::new(Grid::new(2, 1), IntVector2 { x: 0, y: 0 }, 1, Direction::Right, true).unwrap();
+        let mut events = Vec::new();
+
+        // the only free cell gets the food, eating it fills the grid
+        tick_intervals(&mut game, 1, &mut events);
+        match game.state() {
+            GameState::Finished { size, cause, .. } => {
+                assert_eq!(size, 2);
+                assert_eq!(cause, DeathCause::FilledGrid);
+            },
+            GameState::Running => panic!("Expected the game to be finished"),
+        }
+        assert_eq!(events.last(), Some(&GameEvent::Died {
+            position: IntVector2 { x: 1, y: 0 },
+            cause: DeathCause::FilledGrid,
+        }));
+        assert_eq!(game.stats().foods_eaten, 1);
+    }
+
+    #[test]
+    fn standing_on_a_portal_leaves_room_for_food() {
+        let portal = Portal { a: IntVector2 { x: 1, y: 0 }, b: IntVector2 { x: 3, y: 0 } };
+        let mut game = Game::new(Grid::new(5, 1), IntVector2 { x: 0, y: 0 }, 3, Direction::Right, true)
+            .unwrap()
+            .with_level(Level { portals: vec![portal], ..Level::default() })
+            .unwrap();
+        let mut events = Vec::new();
+
+        // the head ends up on the portal's exit, (2, 0) and (4, 0) are still free
+        tick_intervals(&mut game, 1, &mut events);
+        assert_eq!(game.snake().current_position(), IntVector2 { x: 3, y: 0 });
+        assert_eq!(game.state(), GameState::Running);
+
+        tick_intervals(&mut game, 1, &mut events);
+        assert_eq!(game.state(), GameState::Running);
+        assert_eq!(game.foods().len(), 1);
+    }
+
+    #[test]
+    fn invalid_levels_are_rejected() {
+        let patrol = Hazard::Patrol { path: vec![IntVector2 { x: 1, y: 1 }], every: 0 };
+        let game = Game::new(Grid::new(5, 5), IntVector2 { x: 0, y: 0 }, 1, Direction::Right, true)
+            .unwrap()
+            .with_level(Level { hazards: vec![patrol], ..Level::default() });
+        assert_eq!(game.err(), Some(LevelError::InvalidHazard { index: 0 }));
+    }
+
+    #[test]
+    fn tail_cutting_bites_off_the_body() {
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 1, y: 1 }, 5, Direction::Right, true)
+            .unwrap()
+            .with_rules(Rules { tail_cutting: true, ..Rules::default() });
+        let mut events = Vec::new();
+        tick_intervals(&mut game, 4, &mut events);
+
+        for &direction in &[Direction::Up, Direction::Left, Direction::Down] {
+            game.store_direction_change(direction);
+            tick_intervals(&mut game, 1, &mut events);
+        }
+
+        let position = IntVector2 { x: 4, y: 1 };
+        assert_eq!(game.state(), GameState::Running);
+        assert_eq!(events.last(), Some(&GameEvent::TailCut { position, removed: 1 }));
+        // the bitten segment was the tail, so only it is cut off
+        let positions: Vec<_> = game.snake().positions().cloned().collect();
+        assert_eq!(positions, vec![
+            position,
+            IntVector2 { x: 4, y: 2 },
+            IntVector2 { x: 0, y: 2 },
+            IntVector2 { x: 0, y: 1 },
+        ]);
+    }
+
+    #[test]
+    fn hazards_only_hit_while_active() {
+        let spikes = Hazard::Spikes { cells: vec![IntVector2 { x: 2, y: 0 }, IntVector2 { x: 3, y: 0 }], on: 1, off: 2 };
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 0, y: 0 }, 1, Direction::Right, true)
+            .unwrap()
+            .with_level(Level { hazards: vec![spikes], ..Level::default() })
+            .unwrap();
+        let mut events = Vec::new();
+
+        // the spikes are down for the second move, and back up for the third
+        assert_eq!(tick_intervals(&mut game, 2, &mut events), GameState::Running);
+        tick_intervals(&mut game, 1, &mut events);
+        let position = IntVector2 { x: 3, y: 0 };
+        let cause = DeathCause::Hazard { position };
+        assert_eq!(events.last(), Some(&GameEvent::Died { position, cause }));
+        assert_eq!(game.snake().current_position(), IntVector2 { x: 2, y: 0 });
+    }
+
+    #[test]
+    fn hazards_moving_onto_the_body_hit_the_snake() {
+        let patrol = Hazard::Patrol {
+            path: vec![IntVector2 { x: 1, y: 3 }, IntVector2 { x: 1, y: 3 }, IntVector2 { x: 1, y: 0 }],
+            every: 1,
+        };
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 0, y: 0 }, 3, Direction::Right, true)
+            .unwrap()
+            .with_level(Level { hazards: vec![patrol], ..Level::default() })
+            .unwrap();
+        let mut events = Vec::new();
+        tick_intervals(&mut game, 2, &mut events);
+
+        let cause = DeathCause::Hazard { position: IntVector2 { x: 1, y: 0 } };
+        match game.state() {
+            GameState::Finished { cause: finished_cause, .. } => assert_eq!(finished_cause, cause),
+            GameState::Running => panic!("Expected the game to be finished"),
+        }
+        assert_eq!(events.last(), Some(&GameEvent::Died { position: IntVector2 { x: 2, y: 0 }, cause }));
+    }
+
+    #[test]
+    fn portals_move_the_head_to_the_other_end() {
+        let portal = Portal { a: IntVector2 { x: 2, y: 0 }, b: IntVector2 { x: 3, y: 3 } };
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 0, y: 0 }, 3, Direction::Right, true)
+            .unwrap()
+            .with_level(Level { portals: vec![portal], ..Level::default() })
+            .unwrap();
+        let mut events = Vec::new();
+        tick_intervals(&mut game, 3, &mut events);
+
+        // the head comes out on the other end and the body follows it through
+        let positions: Vec<_> = game.snake().positions().cloned().collect();
+        assert_eq!(positions, vec![
+            IntVector2 { x: 4, y: 3 },
+            IntVector2 { x: 3, y: 3 },
+            IntVector2 { x: 1, y: 0 },
+        ]);
+        assert_eq!(game.direction(), Direction::Right);
+        assert_eq!(game.state(), GameState::Running);
+    }
+
+    #[test]
+    fn portals_lead_both_ways() {
+        let portal = Portal { a: IntVector2 { x: 2, y: 0 }, b: IntVector2 { x: 3, y: 3 } };
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 0, y: 0 }, 1, Direction::Right, true)
+            .unwrap()
+            .with_level(Level { portals: vec![portal], ..Level::default() })
+            .unwrap();
+        let mut events = Vec::new();
+
+        let mut heads = Vec::new();
+        let turns = [None, None, None, Some(Direction::Down), Some(Direction::Left), Some(Direction::Up), None];
+        for turn in &turns {
+            if let Some(direction) = *turn {
+                game.store_direction_change(direction);
+            }
+            tick_intervals(&mut game, 1, &mut events);
+            let head = game.snake().current_position();
+            heads.push((head.x, head.y));
+        }
+
+        // in through `a` and out of `b`, then back in through `b` and out of `a`
+        assert_eq!(heads, vec![(1, 0), (3, 3), (4, 3), (4, 2), (3, 2), (2, 0), (2, 1)]);
+        assert_eq!(game.state(), GameState::Running);
+    }
+
+    #[test]
+    fn speed_shortens_the_interval() {
+        let start_position = IntVector2 { x: 0, y: 0 };
+        let mut game = Game::new(Grid::new(5, 5), start_position, 1, Direction::Right, true)
+            .unwrap()
+            .with_rules(Rules { speed: 2.0, ..Rules::default() });
+        let mut random = SequentialRandom { next: 0 };
+        let mut events = Vec::new();
+
+        // too soon for the normal speed, but long enough at double speed
+        game.tick(0.0, &mut random, &mut events);
+        game.tick(ACTION_INTERVAL_BASE * 0.75, &mut random, &mut events);
+        assert_eq!(game.snake().current_position(), IntVector2 { x: 1, y: 0 });
+    }
+
+    #[test]
+    fn time_attack_ends_when_the_time_runs_out() {
+        let rules = Rules {
+            mode: GameMode::TimeAttack,
+            time_attack: TimeAttackRules { duration: 3.0, bonus_time: 2.0, bonus_food_every: 1 },
+            ..Rules::default()
+        };
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 0, y: 2 }, 1, Direction::Right, true)
+            .unwrap()
+            .with_rules(rules);
+        let mut random = SequentialRandom { next: 0 };
+        let mut events = Vec::new();
+
+        game.tick(0.0, &mut random, &mut events);
+        assert!(game.foods()[0].is_bonus());
+        assert_eq!(*game.foods()[0].pos(), IntVector2 { x: 1, y: 2 });
+
+        // eating the bonus food right away adds to the time limit
+        game.tick(1.0, &mut random, &mut events);
+        assert_eq!(game.stats().foods_eaten, 1);
+        assert_eq!(game.time_left(), Some(4.0));
+
+        assert_eq!(game.tick(4.9, &mut random, &mut events), GameState::Running);
+        match game.tick(5.0, &mut random, &mut events) {
+            GameState::Finished { cause, .. } => assert_eq!(cause, DeathCause::OutOfTime),
+            GameState::Running => panic!("Expected the time to run out"),
+        }
+        assert_eq!(game.time_left(), Some(0.0));
+    }
+
+    #[test]
+    fn zen_passes_through_itself_at_a_constant_speed() {
+        let rules = Rules { mode: GameMode::Zen, ..Rules::default() };
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 1, y: 1 }, 5, Direction::Right, true)
+            .unwrap()
+            .with_rules(rules);
+        let mut events = Vec::new();
+        tick_intervals(&mut game, 4, &mut events);
+
+        // the same moves that run into itself in `running_into_itself_finishes_the_game`
+        for &direction in &[Direction::Up, Direction::Left, Direction::Down] {
+            game.store_direction_change(direction);
+            tick_intervals(&mut game, 1, &mut events);
+        }
+        assert_eq!(game.state(), GameState::Running);
+        assert_eq!(game.snake().current_position(), IntVector2 { x: 4, y: 1 });
+
+        // the first food is right in front of the snake
+        let mut game = Game::new(Grid::new(5, 5), IntVector2 { x: 0, y: 2 }, 1, Direction::Right, true)
+            .unwrap()
+            .with_rules(rules);
+        tick_intervals(&mut game, 1, &mut events);
+        assert_eq!(game.stats().foods_eaten, 1);
+        assert_eq!(game.interval_time, ACTION_INTERVAL_BASE);
+    }
+}
@@ -0,0 +1,135 @@
+use wrg_2d::{Direction, Grid, IntVector2};
+use level::LevelError;
+
+/// Something in a level that moves or changes on its own, one step each time
+/// the snake moves. Where a hazard is only depends on how many moves there
+/// have been, so replays see the same hazards.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Hazard {
+    /// A block moving to the next cell of `path` every `every` moves,
+    /// starting over from the first cell after the last
+    Patrol {
+        path: Vec<IntVector2>,
+        every: usize,
+    },
+    /// A bar of `length` cells starting at `pivot`, turning a quarter
+    /// clockwise every `every` moves
+    RotatingBar {
+        pivot: IntVector2,
+        length: usize,
+        start_direction: Direction,
+        every: usize,
+    },
+    /// Cells that are dangerous for `on` moves and then safe for `off`
+    /// moves, starting out dangerous
+    Spikes {
+        cells: Vec<IntVector2>,
+        on: usize,
+        off: usize,
+    },
+}
+
+/// A cell covered by a hazard, only active cells hurt the snake
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HazardCell {
+    pub position: IntVector2,
+    pub active: bool,
+}
+
+impl Hazard {
+    /// Pushes the cells this hazard covers after `step` moves into `cells`
+    pub fn cells_at(&self, grid: &Grid, step: usize, cells: &mut Vec<HazardCell>) {
+        match self {
+            Hazard::Patrol { path, every } => {
+                let position = path[(step / every) % path.len()];
+                cells.push(HazardCell { position, active: true });
+            },
+            Hazard::RotatingBar { pivot, length, start_direction, every } => {
+                let mut direction = *start_direction;
+                for _ in 0..(step / every) % 4 {
+                    direction = direction.rotate_cw();
+                }
+
+                let mut position = *pivot;
+                for _ in 0..*length {
+                    cells.push(HazardCell { position: grid.wrap(position), active: true });
+                    position += direction.to_position();
+                }
+            },
+            Hazard::Spikes { cells: spikes, on, off } => {
+                let active = step % (on + off) < *on;
+                cells.extend(spikes.iter().map(|&position| HazardCell { position, active }));
+            },
+        }
+    }
+
+    /// Checks that the hazard can be placed on `grid`, `index` is where
+    /// the hazard is in its level
+    pub fn validate(&self, grid: &Grid, index: usize) -> Result<(), LevelError> {
+        let (positions, valid) = match self {
+            Hazard::Patrol { path, every } => (path.clone(), !path.is_empty() && *every > 0),
+            Hazard::RotatingBar { pivot, length, every, .. } => (vec![*pivot], *length > 0 && *every > 0),
+            Hazard::Spikes { cells, on, .. } => (cells.clone(), !cells.is_empty() && *on > 0),
+        };
+
+        if let Some(&position) = positions.iter().find(|position| !grid.contains(**position)) {
+            return Err(LevelError::HazardOffGrid { position });
+        }
+
+        if !valid {
+            return Err(LevelError::InvalidHazard { index });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(hazard: &Hazard, step: usize) -> Vec<(IntVector2, bool)> {
+        let mut cells = Vec::new();
+        hazard.cells_at(&Grid::new(5, 5), step, &mut cells);
+        cells.iter().map(|cell| (cell.position, cell.active)).collect()
+    }
+
+    #[test]
+    fn patrols_loop_along_their_path() {
+        let hazard = Hazard::Patrol {
+            path: vec![IntVector2 { x: 1, y: 1 }, IntVector2 { x: 2, y: 1 }, IntVector2 { x: 3, y: 1 }],
+            every: 2,
+        };
+        assert_eq!(positions(&hazard, 0), vec![(IntVector2 { x: 1, y: 1 }, true)]);
+        assert_eq!(positions(&hazard, 3), vec![(IntVector2 { x: 2, y: 1 }, true)]);
+        assert_eq!(positions(&hazard, 4), vec![(IntVector2 { x: 3, y: 1 }, true)]);
+        assert_eq!(positions(&hazard, 6), vec![(IntVector2 { x: 1, y: 1 }, true)]);
+    }
+
+    #[test]
+    fn rotating_bars_turn_and_wrap() {
+        let hazard = Hazard::RotatingBar {
+            pivot: IntVector2 { x: 4, y: 2 },
+            length: 2,
+            start_direction: Direction::Up,
+            every: 1,
+        };
+        assert_eq!(positions(&hazard, 0), vec![
+            (IntVector2 { x: 4, y: 2 }, true),
+            (IntVector2 { x: 4, y: 3 }, true),
+        ]);
+        // pointing right goes over the edge of the grid
+        assert_eq!(positions(&hazard, 5), vec![
+            (IntVector2 { x: 4, y: 2 }, true),
+            (IntVector2 { x: 0, y: 2 }, true),
+        ]);
+    }
+
+    #[test]
+    fn spikes_toggle() {
+        let hazard = Hazard::Spikes { cells: vec![IntVector2 { x: 0, y: 0 }], on: 2, off: 1 };
+        let active: Vec<_> = (0..6).map(|step| positions(&hazard, step)[0].1).collect();
+        assert_eq!(active, vec![true, true, false, true, true, false]);
+    }
+}
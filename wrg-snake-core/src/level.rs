@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::fmt;
+use wrg_2d::{Grid, IntVector2};
+use hazard::{Hazard, HazardCell};
+
+/// What's placed on the grid besides the snake and its food
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Level {
+    pub portals: Vec<Portal>,
+    pub hazards: Vec<Hazard>,
+}
+
+/// Two linked cells, moving into either one puts the head on the other
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Portal {
+    pub a: IntVector2,
+    pub b: IntVector2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelError {
+    PortalOffGrid {
+        position: IntVector2,
+    },
+    /// Both ends of a portal are the same cell
+    PortalToItself {
+        position: IntVector2,
+    },
+    /// A cell is the end of more than one portal
+    OverlappingPortals {
+        position: IntVector2,
+    },
+    /// The ends of a portal are next to each other, so stepping out of one end
+    /// into the other would put the head right back where it was
+    AdjacentPortalEnds {
+        position: IntVector2,
+    },
+    HazardOffGrid {
+        position: IntVector2,
+    },
+    /// The hazard at `index` has no cells, or a timing of 0 moves
+    InvalidHazard {
+        index: usize,
+    },
+}
+
+impl Portal {
+    /// The other end of the portal, if `position` is one of its ends
+    pub fn partner(&self, position: IntVector2) -> Option<IntVector2> {
+        if position == self.a {
+            Some(self.b)
+        } else if position == self.b {
+            Some(self.a)
+        } else {
+            None
+        }
+    }
+}
+
+impl Level {
+    /// Where the head ends up after moving into `position`, if there's a portal there
+    pub fn portal_exit(&self, position: IntVector2) -> Option<IntVector2> {
+        self.portals.iter().filter_map(|portal| portal.partner(position)).next()
+    }
+
+    /// Every cell that's the end of a portal, food is never placed on these
+    pub fn portal_cells(&self) -> impl Iterator<Item=&IntVector2> {
+        self.portals.iter().map(|portal| &portal.a)
+            .chain(self.portals.iter().map(|portal| &portal.b))
+    }
+
+    /// Every cell covered by a hazard after the snake moved `step` times
+    pub fn hazard_cells(&self, grid: &Grid, step: usize) -> Vec<HazardCell> {
+        let mut cells = Vec::new();
+        for hazard in &self.hazards {
+            hazard.cells_at(grid, step, &mut cells);
+        }
+        cells
+    }
+
+    /// Checks that the level can be played on `grid`
+    pub fn validate(&self, grid: &Grid) -> Result<(), LevelError> {
+        for (index, hazard) in self.hazards.iter().enumerate() {
+            hazard.validate(grid, index)?;
+        }
+
+        for portal in &self.portals {
+            if portal.a == portal.b {
+                return Err(LevelError::PortalToItself { position: portal.a });
+            }
+        }
+
+        let mut seen = Vec::new();
+        for &position in self.portal_cells() {
+            if !grid.contains(position) {
+                return Err(LevelError::PortalOffGrid { position });
+            }
+            if seen.contains(&position) {
+                return Err(LevelError::OverlappingPortals { position });
+            }
+            seen.push(position);
+        }
+
+        for portal in &self.portals {
+            if grid.torus_distance(portal.a, portal.b) == 1 {
+                return Err(LevelError::AdjacentPortalEnds { position: portal.a });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::PortalOffGrid { position } => {
+                write!(f, "portal at ({}, {}) is outside of the grid", position.x, position.y)
+            },
+            LevelError::PortalToItself { position } => {
+                write!(f, "portal at ({}, {}) leads to itself", position.x, position.y)
+            },
+            LevelError::OverlappingPortals { position } => {
+                write!(f, "more than one portal at ({}, {})", position.x, position.y)
+            },
+            LevelError::AdjacentPortalEnds { position } => {
+                write!(f, "portal at ({}, {}) has its other end right next to it", position.x, position.y)
+            },
+            LevelError::HazardOffGrid { position } => {
+                write!(f, "hazard at ({}, {}) is outside of the grid", position.x, position.y)
+            },
+            LevelError::InvalidHazard { index } => {
+                write!(f, "hazard {} needs at least one cell and timings of at least 1 move", index)
+            },
+        }
+    }
+}
+
+impl Error for LevelError {
+    fn description(&self) -> &str {
+        match self {
+            LevelError::PortalOffGrid { .. } => "portal is outside of the grid",
+            LevelError::PortalToItself { .. } => "portal leads to itself",
+            LevelError::OverlappingPortals { .. } => "more than one portal in a cell",
+            LevelError::AdjacentPortalEnds { .. } => "portal ends are next to each other",
+            LevelError::HazardOffGrid { .. } => "hazard is outside of the grid",
+            LevelError::InvalidHazard { .. } => "hazard has no cells or a timing of 0 moves",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portals_lead_both_ways() {
+        let level = Level {
+            portals: vec![Portal { a: IntVector2 { x: 0, y: 0 }, b: IntVector2 { x: 3, y: 2 } }],
+            ..Level::default()
+        };
+        assert_eq!(level.portal_exit(IntVector2 { x: 0, y: 0 }), Some(IntVector2 { x: 3, y: 2 }));
+        assert_eq!(level.portal_exit(IntVector2 { x: 3, y: 2 }), Some(IntVector2 { x: 0, y: 0 }));
+        assert_eq!(level.portal_exit(IntVector2 { x: 1, y: 0 }), None);
+    }
+
+    #[test]
+    fn validate_catches_broken_portals() {
+        let grid = Grid::new(4, 4);
+        let portal = |a: (i32, i32), b: (i32, i32)| Portal {
+            a: IntVector2 { x: a.0, y: a.1 },
+            b: IntVector2 { x: b.0, y: b.1 },
+        };
+
+        assert_eq!(Level::default().validate(&grid), Ok(()));
+        // ends of different portals can be next to each other
+        let level = Level { portals: vec![portal((0, 0), (2, 2)), portal((1, 0), (3, 3))], ..Level::default() };
+        assert_eq!(level.validate(&grid), Ok(()));
+
+        let level = Level { portals: vec![portal((1, 0), (2, 0))], ..Level::default() };
+        assert_eq!(level.validate(&grid), Err(LevelError::AdjacentPortalEnds { position: IntVector2 { x: 1, y: 0 } }));
+
+        // the grid wraps around, so ends on opposite edges are next to each other too
+        let level = Level { portals: vec![portal((0, 1), (3, 1))], ..Level::default() };
+        assert_eq!(level.validate(&grid), Err(LevelError::AdjacentPortalEnds { position: IntVector2 { x: 0, y: 1 } }));
+
+        let level = Level { portals: vec![portal((0, 0), (4, 0))], ..Level::default() };
+        assert_eq!(level.validate(&grid), Err(LevelError::PortalOffGrid { position: IntVector2 { x: 4, y: 0 } }));
+
+        let level = Level { portals: vec![portal((1, 1), (1, 1))], ..Level::default() };
+        assert_eq!(level.validate(&grid), Err(LevelError::PortalToItself { position: IntVector2 { x: 1, y: 1 } }));
+
+        let level = Level { portals: vec![portal((0, 0), (3, 3)), portal((3, 3), (2, 0))], ..Level::default() };
+        assert_eq!(level.validate(&grid), Err(LevelError::OverlappingPortals { position: IntVector2 { x: 3, y: 3 } }));
+    }
+
+    #[test]
+    fn validate_catches_broken_hazards() {
+        let grid = Grid::new(4, 4);
+        let spikes = |x: i32, on: usize| Hazard::Spikes { cells: vec![IntVector2 { x, y: 0 }], on, off: 0 };
+
+        let level = Level { hazards: vec![spikes(3, 1)], ..Level::default() };
+        assert_eq!(level.validate(&grid), Ok(()));
+
+        let level = Level { hazards: vec![spikes(3, 1), spikes(4, 1)], ..Level::default() };
+        assert_eq!(level.validate(&grid), Err(LevelError::HazardOffGrid { position: IntVector2 { x: 4, y: 0 } }));
+
+        let level = Level { hazards: vec![spikes(3, 1), spikes(2, 0)], ..Level::default() };
+        assert_eq!(level.validate(&grid), Err(LevelError::InvalidHazard { index: 1 }));
+    }
+}
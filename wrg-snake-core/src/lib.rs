@@ -0,0 +1,29 @@
+//! The rules of snake, independent of how the game is rendered or where
+//! its input comes from.
+#[macro_use]
+extern crate serde;
+extern crate wrg_2d;
+#[cfg(test)]
+extern crate serde_json;
+
+mod food;
+mod game;
+mod hazard;
+mod level;
+mod random;
+mod replay;
+mod rules;
+mod setup;
+mod snake;
+mod stats;
+
+pub use food::Food;
+pub use game::{DeathCause, Game, GameEvent, GameState};
+pub use hazard::{Hazard, HazardCell};
+pub use level::{Level, LevelError, Portal};
+pub use random::{RandomSource, XorShiftRandom};
+pub use replay::Replay;
+pub use rules::{GameMode, Rules, TimeAttackRules};
+pub use setup::{SetupError, validate_setup};
+pub use snake::{PlayerSnakeController, Snake, SnakeActionMoveTo, SnakeError};
+pub use stats::GameStats;
@@ -0,0 +1,46 @@
+/// Where the game gets its random numbers from, so the rules don't depend
+/// on a specific platform's random number generator
+pub trait RandomSource {
+    /// A random number in `low..high`
+    fn gen_range(&mut self, low: usize, high: usize) -> usize;
+}
+
+/// Small seeded generator, food placement doesn't need anything better and
+/// the same seed always places food in the same cells, which replays rely on
+#[derive(Debug, Clone)]
+pub struct XorShiftRandom {
+    state: u64,
+}
+
+impl XorShiftRandom {
+    pub fn new(seed: u64) -> XorShiftRandom {
+        // xorshift gets stuck at zero
+        let state = if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed };
+        XorShiftRandom { state }
+    }
+}
+
+impl RandomSource for XorShiftRandom {
+    fn gen_range(&mut self, low: usize, high: usize) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        low + (self.state % (high - low) as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_numbers() {
+        let mut a = XorShiftRandom::new(42);
+        let mut b = XorShiftRandom::new(42);
+        for _ in 0..100 {
+            let number = a.gen_range(3, 10);
+            assert_eq!(number, b.gen_range(3, 10));
+            assert!(number >= 3 && number < 10);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use wrg_2d::{Direction, Grid, IntVector2};
+use game::{Game, GameEvent, GameState};
+use level::Level;
+use random::XorShiftRandom;
+use rules::Rules;
+use setup::{SetupError, validate_setup};
+
+/// Everything needed to play a game again exactly as it happened, a game
+/// placing its food with `XorShiftRandom::new(seed)` can be saved as a replay
+/// with the moves from `Game::moves()`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub grid: Grid,
+    pub start_position: IntVector2,
+    pub start_length: usize,
+    pub start_direction: Direction,
+    pub seed: u64,
+    pub moves: Vec<Direction>,
+    /// When each move happened from `Game::move_times()`, replays recorded
+    /// without them play one move every interval
+    #[serde(default)]
+    pub move_times: Vec<f64>,
+    /// Replays recorded before rules could be changed were played with the defaults
+    #[serde(default)]
+    pub rules: Rules,
+    /// Replays recorded before levels were added were played on an empty grid
+    #[serde(default)]
+    pub level: Level,
+}
+
+impl Replay {
+    /// Checks that the game in the replay can be played, replays are loaded
+    /// from files so this is also done by `play()`
+    pub fn validate(&self) -> Result<(), SetupError> {
+        validate_setup(&self.grid, self.start_position, self.start_length, &self.rules, &self.level)
+    }
+
+    /// Plays the replay from the start, calling `on_move` with the starting state
+    /// and then again after every move with the events that move caused, and
+    /// once more if the game ran out of time.
+    ///
+    /// Returns the game as it was after the last move.
+    pub fn play<F: FnMut(&Game, &[GameEvent])>(&self, mut on_move: F) -> Result<Game, SetupError> {
+        self.validate()?;
+        let mut game = Game::new(self.grid, self.start_position, self.start_length, self.start_direction, true)
+            .expect("start_length is validated against the grid area")
+            .with_rules(self.rules)
+            .with_level(self.level.clone())
+            .expect("the level is validated");
+        let mut random = XorShiftRandom::new(self.seed);
+        let mut events = Vec::new();
+
+        // the food for the first move is placed before the snake moves
+        game.tick(0.0, &mut random, &mut events);
+        on_move(&game, &events);
+
+        let has_times = self.move_times.len() == self.moves.len();
+        for (index, &direction) in self.moves.iter().enumerate() {
+            events.clear();
+            game.store_direction_change(direction);
+            // ticking at the same time as the original move moves the snake the same way,
+            // without times half an interval past the next move moves it exactly once
+            let now = if has_times {
+                self.move_times[index]
+            } else {
+                game.move_times().last().cloned().unwrap_or(0.0) + game.interval() * 1.5
+            };
+            game.tick(now, &mut random, &mut events);
+            on_move(&game, &events);
+        }
+
+        // a game running out of time ends without moving
+        if let Some(time_left) = game.time_left() {
+            if game.state() == GameState::Running {
+                events.clear();
+                let now = game.stats().duration + time_left;
+                game.tick(now, &mut random, &mut events);
+                on_move(&game, &events);
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+    use game::DeathCause;
+    use hazard::Hazard;
+    use level::{LevelError, Portal};
+    use rules::GameMode;
+
+    fn record(rules: Rules, level: Level) -> (Game, Replay) {
+        record_from(0.0, rules, level)
+    }
+
+    /// Wanders around the grid until the game ends, with uneven tick times
+    /// starting at `start`
+    fn record_from(start: f64, rules: Rules, level: Level) -> (Game, Replay) {
+        let grid = Grid::new(6, 6);
+        let start_position = IntVector2 { x: 0, y: 2 };
+        let seed = 7;
+        let mut game = Game::new(grid, start_position, 3, Direction::Right, true)
+            .unwrap()
+            .with_rules(rules)
+            .with_level(level.clone())
+            .unwrap();
+        let mut random = XorShiftRandom::new(seed);
+        let mut events = Vec::new();
+
+        let turns = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+        let mut now = start;
+        let mut tick = 0;
+        while game.state() == GameState::Running && tick < 1000 {
+            if tick % 7 == 0 {
+                game.store_direction_change(turns[(tick / 7) % turns.len()]);
+            }
+            now += 0.03 + (tick % 3) as f64 * 0.05;
+            game.tick(now, &mut random, &mut events);
+            tick += 1;
+        }
+
+        let replay = Replay {
+            grid,
+            start_position,
+            start_length: 3,
+            start_direction: Direction::Right,
+            seed,
+            moves: game.moves().to_vec(),
+            move_times: game.move_times().to_vec(),
+            rules,
+            level,
+        };
+        (game, replay)
+    }
+
+    #[test]
+    fn replays_the_same_game() {
+        let portal = Portal { a: IntVector2 { x: 2, y: 2 }, b: IntVector2 { x: 4, y: 5 } };
+        let (game, replay) = record(Rules::default(), Level { portals: vec![portal], ..Level::default() });
+
+        let mut moves_played = 0;
+        let replayed = replay.play(|_, _| moves_played += 1).unwrap();
+        assert_eq!(moves_played, replay.moves.len() + 1);
+        assert_eq!(replayed.moves(), game.moves());
+        assert_eq!(replayed.snake().length(), game.snake().length());
+        assert_eq!(
+            replayed.snake().positions().collect::<Vec<_>>(),
+            game.snake().positions().collect::<Vec<_>>(),
+        );
+
+        // times can come back off by a rounding error, which mustn't change the game
+        let json = serde_json::to_string(&replay).unwrap();
+        let loaded: Replay = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.play(|_, _| ()).unwrap().moves(), game.moves());
+    }
+
+    #[test]
+    fn replays_hitting_a_hazard() {
+        let bar = Hazard::RotatingBar {
+            pivot: IntVector2 { x: 3, y: 3 },
+            length: 3,
+            start_direction: Direction::Left,
+            every: 2,
+        };
+        let (game, replay) = record(Rules::default(), Level { hazards: vec![bar], ..Level::default() });
+
+        let replayed = replay.play(|_, _| ()).unwrap();
+        assert_eq!(replayed.moves(), game.moves());
+        match (replayed.state(), game.state()) {
+            (GameState::Finished { cause, .. }, GameState::Finished { cause: recorded_cause, .. }) => {
+                assert_eq!(cause, recorded_cause);
+                assert_eq!(cause, DeathCause::Hazard { position: IntVector2 { x: 3, y: 3 } });
+            },
+            _ => panic!("Expected the snake to hit the bar"),
+        }
+    }
+
+    #[test]
+    fn replays_a_game_recorded_later_on() {
+        // the first tick comes long after an interval, which mustn't move the snake
+        let (game, replay) = record_from(1000.0, Rules::default(), Level::default());
+        assert_eq!(replay.move_times[0], game.move_times()[0]);
+        assert!(replay.move_times[0] > 0.0);
+
+        let replayed = replay.play(|_, _| ()).unwrap();
+        assert_eq!(replayed.moves(), game.moves());
+        assert_eq!(replayed.stats().foods_eaten, game.stats().foods_eaten);
+        assert_eq!(
+            replayed.snake().positions().collect::<Vec<_>>(),
+            game.snake().positions().collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn replays_without_move_times() {
+        // an interval this slow is longer than a second
+        let (game, mut replay) = record(Rules { speed: 0.1, ..Rules::default() }, Level::default());
+        replay.move_times.clear();
+
+        let replayed = replay.play(|_, _| ()).unwrap();
+        assert_eq!(replayed.moves(), game.moves());
+        assert_eq!(replayed.stats().foods_eaten, game.stats().foods_eaten);
+        assert_eq!(
+            replayed.snake().positions().collect::<Vec<_>>(),
+            game.snake().positions().collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn invalid_replays_are_not_played() {
+        let (_, replay) = record(Rules::default(), Level::default());
+        assert_eq!(replay.validate(), Ok(()));
+
+        let mut off_grid = replay.clone();
+        off_grid.start_position = IntVector2 { x: 0, y: 9 };
+        assert_eq!(off_grid.play(|_, _| ()).err(), Some(SetupError::StartPositionOffGrid {
+            start_position: IntVector2 { x: 0, y: 9 },
+            grid: replay.grid,
+        }));
+
+        let mut too_long = replay.clone();
+        too_long.start_length = 37;
+        assert_eq!(too_long.validate(), Err(SetupError::StartLengthExceedsGrid { start_length: 37, grid_area: 36 }));
+
+        let mut no_grid = replay.clone();
+        no_grid.grid = Grid::new(0, 6);
+        assert_eq!(no_grid.validate(), Err(SetupError::ZeroSizedGrid { grid: no_grid.grid }));
+
+        let mut stopped = replay.clone();
+        stopped.rules.speed = 0.0;
+        assert_eq!(stopped.validate(), Err(SetupError::InvalidSpeed { speed: 0.0 }));
+
+        let mut broken_level = replay.clone();
+        broken_level.level.hazards.push(Hazard::Patrol { path: vec![IntVector2 { x: 1, y: 1 }], every: 0 });
+        assert_eq!(broken_level.validate(), Err(SetupError::InvalidLevel(LevelError::InvalidHazard { index: 0 })));
+    }
+
+    #[test]
+    fn replays_running_out_of_time() {
+        let mut rules = Rules { mode: GameMode::TimeAttack, ..Rules::default() };
+        rules.time_attack.duration = 1.5;
+        let (game, replay) = record(rules, Level::default());
+
+        let replayed = replay.play(|_, _| ()).unwrap();
+        assert_eq!(replayed.moves(), game.moves());
+        match replayed.state() {
+            GameState::Finished { cause, .. } => assert_eq!(cause, DeathCause::OutOfTime),
+            GameState::Running => panic!("Expected the replay to run out of time"),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/// Options that change how a game plays, on top of the size of the grid
+/// and the snake the game starts with
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    /// How fast the snake moves compared to normal, the time between
+    /// moves is divided by this
+    pub speed: f64,
+    pub mode: GameMode,
+    /// Biting the snake's own body cuts it off there instead of ending the game
+    pub tail_cutting: bool,
+    /// Only used in `GameMode::TimeAttack`
+    pub time_attack: TimeAttackRules,
+}
+
+/// How a game ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// Play until the snake dies
+    Classic,
+    /// Score as much as possible before the time runs out
+    TimeAttack,
+    /// Practice where the snake passes through itself and never speeds up
+    Zen,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeAttackRules {
+    /// Seconds the player has at the start
+    pub duration: f64,
+    /// Seconds added for eating bonus food
+    pub bonus_time: f64,
+    /// Every this many foods is a bonus food, 0 for no bonus food
+    pub bonus_food_every: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules {
+            speed: 1.0,
+            mode: GameMode::Classic,
+            tail_cutting: false,
+            time_attack: TimeAttackRules::default(),
+        }
+    }
+}
+
+impl Default for TimeAttackRules {
+    fn default() -> TimeAttackRules {
+        TimeAttackRules {
+            duration: 60.0,
+            bonus_time: 5.0,
+            bonus_food_every: 5,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::fmt;
+use wrg_2d::{Grid, IntVector2};
+use level::{Level, LevelError};
+use rules::{GameMode, Rules};
+
+/// Why a game can't be started on a grid with a snake, rules and level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetupError {
+    ZeroSizedGrid {
+        grid: Grid,
+    },
+    StartPositionOffGrid {
+        start_position: IntVector2,
+        grid: Grid,
+    },
+    InvalidLevel(LevelError),
+    StartPositionOnPortal {
+        start_position: IntVector2,
+    },
+    StartPositionOnHazard {
+        start_position: IntVector2,
+    },
+    ZeroStartLength,
+    StartLengthExceedsGrid {
+        start_length: usize,
+        grid_area: usize,
+    },
+    InvalidSpeed {
+        speed: f64,
+    },
+    InvalidTimeLimit {
+        duration: f64,
+        bonus_time: f64,
+    },
+}
+
+/// Checks that a game can be started with a snake of `start_length` at
+/// `start_position`, configs and replays come from outside so they're
+/// checked with this before being played
+pub fn validate_setup(
+    grid: &Grid,
+    start_position: IntVector2,
+    start_length: usize,
+    rules: &Rules,
+    level: &Level,
+) -> Result<(), SetupError>
+{
+    if grid.width == 0 || grid.height == 0 {
+        return Err(SetupError::ZeroSizedGrid { grid: *grid });
+    }
+
+    if !grid.contains(start_position) {
+        return Err(SetupError::StartPositionOffGrid { start_position, grid: *grid });
+    }
+
+    level.validate(grid).map_err(SetupError::InvalidLevel)?;
+    if level.portal_exit(start_position).is_some() {
+        return Err(SetupError::StartPositionOnPortal { start_position });
+    }
+    if level.hazard_cells(grid, 0).iter().any(|cell| cell.position == start_position) {
+        return Err(SetupError::StartPositionOnHazard { start_position });
+    }
+
+    if start_length == 0 {
+        return Err(SetupError::ZeroStartLength);
+    }
+
+    let grid_area = grid.area();
+    if start_length > grid_area {
+        return Err(SetupError::StartLengthExceedsGrid { start_length, grid_area });
+    }
+
+    if rules.speed.is_nan() || rules.speed <= 0.0 {
+        return Err(SetupError::InvalidSpeed { speed: rules.speed });
+    }
+
+    // written so NaN is invalid too
+    let time_attack = &rules.time_attack;
+    let valid_time_limit = time_attack.duration > 0.0 && time_attack.bonus_time >= 0.0;
+    if rules.mode == GameMode::TimeAttack && !valid_time_limit {
+        return Err(SetupError::InvalidTimeLimit {
+            duration: time_attack.duration,
+            bonus_time: time_attack.bonus_time,
+        });
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetupError::ZeroSizedGrid { grid } => write!(
+                f,
+                "grid must be at least 1x1, got {}x{}",
+                grid.width, grid.height,
+            ),
+            SetupError::StartPositionOffGrid { start_position, grid } => write!(
+                f,
+                "start_position ({}, {}) is outside of the {}x{} grid",
+                start_position.x, start_position.y, grid.width, grid.height,
+            ),
+            SetupError::InvalidLevel(err) => write!(f, "invalid level: {}", err),
+            SetupError::StartPositionOnPortal { start_position } => write!(
+                f,
+                "start_position ({}, {}) is on a portal",
+                start_position.x, start_position.y,
+            ),
+            SetupError::StartPositionOnHazard { start_position } => write!(
+                f,
+                "start_position ({}, {}) is on a hazard",
+                start_position.x, start_position.y,
+            ),
+            SetupError::ZeroStartLength => write!(f, "start_length must be at least 1"),
+            SetupError::StartLengthExceedsGrid { start_length, grid_area } => write!(
+                f,
+                "start_length {} does not fit in a grid with {} cells",
+                start_length, grid_area,
+            ),
+            SetupError::InvalidSpeed { speed } => write!(f, "rules.speed must be above 0, got {}", speed),
+            SetupError::InvalidTimeLimit { duration, bonus_time } => write!(
+                f,
+                "rules.time_attack needs a duration above 0 and a bonus_time of at least 0, got {} and {}",
+                duration, bonus_time,
+            ),
+        }
+    }
+}
+
+impl Error for SetupError {
+    fn description(&self) -> &str {
+        match self {
+            SetupError::ZeroSizedGrid { .. } => "grid must be at least 1x1",
+            SetupError::StartPositionOffGrid { .. } => "start_position is outside of the grid",
+            SetupError::InvalidLevel(_) => "invalid level",
+            SetupError::StartPositionOnPortal { .. } => "start_position is on a portal",
+            SetupError::StartPositionOnHazard { .. } => "start_position is on a hazard",
+            SetupError::ZeroStartLength => "start_length must be at least 1",
+            SetupError::StartLengthExceedsGrid { .. } => "start_length does not fit in the grid",
+            SetupError::InvalidSpeed { .. } => "rules.speed must be above 0",
+            SetupError::InvalidTimeLimit { .. } => "rules.time_attack has an invalid time limit",
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use super::Snake;
-use wasm_rgame::{KeyManager, key_codes};
 use wrg_2d::{IntVector2, Direction};
 
 pub struct SnakeActionMoveTo {
@@ -21,20 +20,9 @@ impl PlayerSnakeController {
         }
     }
 
-    pub fn store_direction_change(&mut self, key_manager: &KeyManager) {
+    /// Queues a turn towards `wanted_direction`, applied at the next `change_direction()`
+    pub fn store_direction_change(&mut self, wanted_direction: Direction) {
         // Don't let direction change if already going opposite direction
-        let wanted_direction = if key_manager.key_down(key_codes::W) {
-            Direction::Up
-        } else if key_manager.key_down(key_codes::D) {
-            Direction::Right
-        } else if key_manager.key_down(key_codes::S) {
-            Direction::Down
-        } else if key_manager.key_down(key_codes::A) {
-            Direction::Left
-        } else {
-            return;
-        };
-
         let valid = self.is_valid(wanted_direction);
         if valid {
             self.next_direction = Some(wanted_direction);
@@ -59,6 +47,11 @@ impl PlayerSnakeController {
         }
     }
 
+    /// The direction the snake last moved in
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
     fn is_valid(&self, new_direction: Direction) -> bool {
         self.direction.opposite() != new_direction
     }
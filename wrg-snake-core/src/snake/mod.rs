@@ -0,0 +1,367 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use wrg_2d::IntVector2;
+
+pub mod controller;
+pub use self::controller::{PlayerSnakeController, SnakeActionMoveTo};
+
+pub struct Snake {
+    /// Most recent position first, never holds more than `capacity` positions
+    /// but only grows as the snake moves, so huge grids don't allocate up front
+    position_history: VecDeque<IntVector2>,
+    /// Repeated after `position_history` when the snake is longer than it
+    start_position: IntVector2,
+    length: usize,
+    capacity: usize,
+
+    /// Where the head and tail were before the last `move_to()`
+    previous_head: IntVector2,
+    previous_tail: IntVector2,
+    /// Where the tail was right after the last `move_to()`, unlike `tail_position()`
+    /// this isn't affected by growing in between moves
+    moved_tail: IntVector2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnakeError {
+    CapacityExceeded {
+        capacity: usize,
+    },
+}
+
+impl Snake {
+    /// Creates a snake that can grow up to `capacity` segments, this is usually
+    /// the area of the grid the snake is in
+    pub fn new(start_position: IntVector2, start_length: usize, capacity: usize) -> Result<Snake, SnakeError> {
+        if start_length > capacity || capacity == 0 {
+            return Err(SnakeError::CapacityExceeded { capacity });
+        }
+
+        let mut position_history = VecDeque::new();
+        position_history.push_front(start_position);
+
+        let mut snake = Snake {
+            start_position,
+            position_history,
+            length: 1,
+            capacity,
+            previous_head: start_position,
+            previous_tail: start_position,
+            moved_tail: start_position,
+        };
+
+        for _ in 1..start_length {
+            snake.grow()?;
+        }
+
+        Ok(snake)
+    }
+
+    pub fn grow(&mut self) -> Result<(), SnakeError> {
+        if self.length >= self.capacity {
+            return Err(SnakeError::CapacityExceeded { capacity: self.capacity });
+        }
+
+        self.length += 1;
+        Ok(())
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Removes up to `n` segments from the tail, the head is always kept
+    pub fn shrink(&mut self, n: usize) {
+        let length = self.length;
+        self.truncate_at(length.saturating_sub(n));
+    }
+
+    /// Keeps only the first `index` segments, the head is always kept. Does nothing
+    /// if the snake isn't longer than `index`.
+    pub fn truncate_at(&mut self, index: usize) {
+        let index = index.max(1);
+        if index >= self.length {
+            return;
+        }
+
+        // the segment after the new tail is where the new tail was before the last move
+        let previous_tail = *self.positions().nth(index).expect("index is less than the length");
+        self.previous_tail = previous_tail;
+        self.length = index;
+        if index <= self.position_history.len() {
+            // growing again piles up on the new tail instead of reviving cut off positions
+            self.position_history.truncate(index);
+            self.start_position = self.tail_position();
+        }
+        self.moved_tail = self.tail_position();
+    }
+
+    pub fn move_to(&mut self, position: IntVector2) {
+        // right now it's not supported to move to the same position,
+        // although I could see a possible use-case for compacting the snake into
+        // a single position after some effect (teleport?)
+        debug_assert!(self.current_position() != position);
+
+        self.previous_head = self.current_position();
+        self.previous_tail = self.moved_tail;
+
+        self.position_history.push_front(position);
+        // positions older than `capacity` can never be part of the snake
+        self.position_history.truncate(self.capacity);
+
+        self.moved_tail = self.tail_position();
+    }
+
+    pub fn current_position(&self) -> IntVector2 {
+        *self.position_history.front().expect("Always at least one position")
+    }
+
+    pub fn tail_position(&self) -> IntVector2 {
+        *self.positions().last().expect("Always at least one position")
+    }
+
+    pub fn previous_head_position(&self) -> IntVector2 {
+        self.previous_head
+    }
+
+    /// Where the tail was before the last move, this is the same as `tail_position()`
+    /// if the snake grew since it keeps its tail in place.
+    pub fn previous_tail_position(&self) -> IntVector2 {
+        self.previous_tail
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item=&IntVector2> {
+        let repeat = self.length.saturating_sub(self.position_history.len());
+        self.position_history.iter().take(self.length())
+            // if length() is larger than position history, then we repeat the start position
+            .chain(repeat_ref(&self.start_position).take(repeat))
+    }
+}
+
+impl fmt::Display for SnakeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnakeError::CapacityExceeded { capacity } => {
+                write!(f, "snake can't be longer than its capacity of {}", capacity)
+            },
+        }
+    }
+}
+
+impl Error for SnakeError {
+    fn description(&self) -> &str {
+        match self {
+            SnakeError::CapacityExceeded { .. } => "snake can't be longer than its capacity",
+        }
+    }
+}
+
+fn repeat_ref<'a, T: 'a>(item: &T) -> RepeatRef<T> {
+    RepeatRef { item }
+}
+
+struct RepeatRef<'a, T: 'a> {
+    item: &'a T,
+}
+
+impl<'a, T: 'a> Iterator for RepeatRef<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wrg_2d::Grid;
+
+    #[test]
+    fn start_long_and_move_around() {
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 3, 100).unwrap();
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+        snake.move_to(IntVector2 { x: 2, y: 0 });
+        snake.move_to(IntVector2 { x: 3, y: 0 });
+        snake.move_to(IntVector2 { x: 4, y: 0 });
+        snake.move_to(IntVector2 { x: 5, y: 0 });
+        snake.move_to(IntVector2 { x: 6, y: 0 });
+
+        for position in snake.positions() {
+            println!("position: {:?}", position);
+        }
+    }
+
+    #[test]
+    fn capacity_is_only_a_limit() {
+        // the biggest grid there can be, allocating all of it would fail
+        let capacity = Grid::new(65535, 65535).area();
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 3, capacity).unwrap();
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+        assert_eq!(snake.length(), 3);
+        assert!(snake.position_history.capacity() < 100);
+
+        let mut small = Snake::new(IntVector2 { x: 0, y: 0 }, 2, 2).unwrap();
+        assert_eq!(small.grow(), Err(SnakeError::CapacityExceeded { capacity: 2 }));
+    }
+
+    #[test]
+    fn grow_can_go_beyond_history() {
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 1, 100).unwrap();
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+        snake.move_to(IntVector2 { x: 2, y: 0 });
+
+        for _ in 0..5 { snake.grow().unwrap(); }
+
+        assert_eq!(snake.length(), 6);
+        let mut positions = snake.positions();
+        assert_eq!(positions.next(), Some(&IntVector2 { x: 2, y: 0 }));
+        assert_eq!(positions.next(), Some(&IntVector2 { x: 1, y: 0 }));
+        assert_eq!(positions.next(), Some(&IntVector2 { x: 0, y: 0 }));
+        assert_eq!(positions.next(), Some(&IntVector2 { x: 0, y: 0 }));
+        assert_eq!(positions.next(), Some(&IntVector2 { x: 0, y: 0 }));
+        assert_eq!(positions.next(), Some(&IntVector2 { x: 0, y: 0 }));
+        assert_eq!(positions.next(), None);
+    }
+
+    #[test]
+    fn normal_condition_test_everything() {
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 1, 100).unwrap();
+        assert_eq!(snake.length(), 1);
+        assert_eq!(snake.current_position(), IntVector2 { x: 0, y: 0 });
+
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+        assert_eq!(snake.length(), 1);
+        assert_eq!(snake.current_position(), IntVector2 { x: 1, y: 0 });
+
+        snake.grow().unwrap();
+        assert_eq!(snake.length(), 2);
+        assert_eq!(snake.current_position(), IntVector2 { x: 1, y: 0 });
+
+        snake.grow().unwrap();
+        assert_eq!(snake.length(), 3);
+        assert_eq!(snake.current_position(), IntVector2 { x: 1, y: 0 });
+
+        snake.move_to(IntVector2 { x: 201, y: -30 });
+        assert_eq!(snake.length(), 3);
+        assert_eq!(snake.current_position(), IntVector2 { x: 201, y: -30 });
+    }
+
+    #[test]
+    fn shrink_can_cut_into_the_repeated_start_position() {
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 6, 100).unwrap();
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+        snake.move_to(IntVector2 { x: 2, y: 0 });
+
+        snake.shrink(2);
+
+        assert_eq!(snake.length(), 4);
+        {
+            let mut positions = snake.positions();
+            assert_eq!(positions.next(), Some(&IntVector2 { x: 2, y: 0 }));
+            assert_eq!(positions.next(), Some(&IntVector2 { x: 1, y: 0 }));
+            assert_eq!(positions.next(), Some(&IntVector2 { x: 0, y: 0 }));
+            assert_eq!(positions.next(), Some(&IntVector2 { x: 0, y: 0 }));
+            assert_eq!(positions.next(), None);
+        }
+
+        // the rest of the start position moves out one move at a time
+        snake.move_to(IntVector2 { x: 3, y: 0 });
+        snake.move_to(IntVector2 { x: 4, y: 0 });
+        let positions: Vec<_> = snake.positions().cloned().collect();
+        assert_eq!(positions, vec![
+            IntVector2 { x: 4, y: 0 },
+            IntVector2 { x: 3, y: 0 },
+            IntVector2 { x: 2, y: 0 },
+            IntVector2 { x: 1, y: 0 },
+        ]);
+    }
+
+    #[test]
+    fn shrink_always_keeps_the_head() {
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 3, 100).unwrap();
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+
+        snake.shrink(10);
+        assert_eq!(snake.length(), 1);
+        assert_eq!(snake.tail_position(), IntVector2 { x: 1, y: 0 });
+
+        snake.truncate_at(0);
+        assert_eq!(snake.length(), 1);
+        assert_eq!(snake.current_position(), IntVector2 { x: 1, y: 0 });
+
+        // growing after shrinking piles up on the head, which is also the tail
+        for _ in 0..2 { snake.grow().unwrap(); }
+        let positions: Vec<_> = snake.positions().cloned().collect();
+        assert_eq!(positions, vec![IntVector2 { x: 1, y: 0 }; 3]);
+    }
+
+    #[test]
+    fn truncate_keeps_the_front_of_the_snake() {
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 4, 100).unwrap();
+        for x in 1..5 {
+            snake.move_to(IntVector2 { x, y: 0 });
+        }
+
+        snake.truncate_at(2);
+        assert_eq!(snake.length(), 2);
+        assert_eq!(snake.tail_position(), IntVector2 { x: 3, y: 0 });
+        assert_eq!(snake.previous_tail_position(), IntVector2 { x: 2, y: 0 });
+
+        // growing keeps the tail in place instead of bringing back the cut segments
+        snake.grow().unwrap();
+        let positions: Vec<_> = snake.positions().cloned().collect();
+        assert_eq!(positions, vec![
+            IntVector2 { x: 4, y: 0 },
+            IntVector2 { x: 3, y: 0 },
+            IntVector2 { x: 3, y: 0 },
+        ]);
+    }
+
+    #[test]
+    fn previous_positions_follow_moves() {
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 2, 100).unwrap();
+        assert_eq!(snake.previous_head_position(), IntVector2 { x: 0, y: 0 });
+        assert_eq!(snake.previous_tail_position(), IntVector2 { x: 0, y: 0 });
+
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+        snake.move_to(IntVector2 { x: 2, y: 0 });
+        assert_eq!(snake.previous_head_position(), IntVector2 { x: 1, y: 0 });
+        assert_eq!(snake.previous_tail_position(), IntVector2 { x: 0, y: 0 });
+        assert_eq!(snake.tail_position(), IntVector2 { x: 1, y: 0 });
+
+        // growing keeps the tail in place for the next move
+        snake.grow().unwrap();
+        snake.move_to(IntVector2 { x: 3, y: 0 });
+        assert_eq!(snake.previous_head_position(), IntVector2 { x: 2, y: 0 });
+        assert_eq!(snake.previous_tail_position(), IntVector2 { x: 1, y: 0 });
+        assert_eq!(snake.tail_position(), IntVector2 { x: 1, y: 0 });
+    }
+
+    #[test]
+    fn capacity_is_never_exceeded() {
+        assert_eq!(
+            Snake::new(IntVector2 { x: 0, y: 0 }, 5, 4).err(),
+            Some(SnakeError::CapacityExceeded { capacity: 4 }),
+        );
+
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 3, 4).unwrap();
+        assert!(snake.grow().is_ok());
+        assert_eq!(snake.grow(), Err(SnakeError::CapacityExceeded { capacity: 4 }));
+        assert_eq!(snake.length(), 4);
+
+        for x in 1..50 {
+            snake.move_to(IntVector2 { x, y: 0 });
+        }
+
+        assert_eq!(snake.position_history.len(), 4);
+        let positions: Vec<_> = snake.positions().cloned().collect();
+        assert_eq!(positions, vec![
+            IntVector2 { x: 49, y: 0 },
+            IntVector2 { x: 48, y: 0 },
+            IntVector2 { x: 47, y: 0 },
+            IntVector2 { x: 46, y: 0 },
+        ]);
+    }
+}
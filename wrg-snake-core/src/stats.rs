@@ -0,0 +1,13 @@
+/// What happened during a game, collected as it's played
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameStats {
+    /// Seconds between the first tick and the end of the game
+    pub duration: f64,
+    pub foods_eaten: usize,
+    /// Fastest the snake moved, in cells per second
+    pub max_speed: f64,
+    /// How many times the snake changed direction
+    pub turns: usize,
+    /// How many different cells the head has been in, including the start
+    pub tiles_visited: usize,
+}
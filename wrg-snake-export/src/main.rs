@@ -0,0 +1,201 @@
+//! Rasterizes replays saved by `wrg-snake-term --record` with the same renderer
+//! the game uses, either a single frame to PNG or the whole replay to a GIF.
+//!
+//! Usage:
+//! `wrg-snake-export png REPLAY OUTPUT [--move N] [--theme NAME]`
+//! `wrg-snake-export gif REPLAY OUTPUT [--theme NAME]`
+extern crate gif;
+extern crate png;
+extern crate serde_json;
+extern crate wrg_snake_core;
+extern crate wrg_snake_render;
+
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::process;
+use wrg_snake_core::{Game, Replay};
+use wrg_snake_render::{
+    EffectSettings, RenderState, SnakeGameRenderer, ThemeHandle, ThemeKind, preferred_canvas_size,
+};
+
+mod raster;
+
+use raster::RasterTarget;
+
+const USAGE: &str = "Usage:
+    wrg-snake-export png REPLAY OUTPUT [--move N] [--theme NAME]
+    wrg-snake-export gif REPLAY OUTPUT [--theme NAME]";
+
+/// How long each move is shown in a GIF, in hundredths of a second. This is
+/// about as fast as the game starts out
+const GIF_MOVE_DELAY : u16 = 12;
+/// Frames drawn for every move of the snake, so the snake slides between cells
+const GIF_FRAMES_PER_MOVE : u16 = 3;
+/// Frames added at the end of a GIF so the death animation can play out
+const GIF_END_FRAMES : u16 = 25;
+
+struct Options {
+    format: String,
+    replay_path: String,
+    output_path: String,
+    /// Only for PNGs, how many moves into the replay to draw
+    last_move: Option<usize>,
+    theme: ThemeKind,
+}
+
+fn main() {
+    let options = match parse_options(env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            process::exit(1);
+        },
+    };
+
+    if let Err(err) = run(&options) {
+        eprintln!("Failed to export {}: {}", options.replay_path, err);
+        process::exit(1);
+    }
+}
+
+fn parse_options(args: Vec<String>) -> Result<Options, String> {
+    if args.len() < 3 {
+        return Err("Expected a format, a replay and an output file".to_string());
+    }
+
+    let mut options = Options {
+        format: args[0].clone(),
+        replay_path: args[1].clone(),
+        output_path: args[2].clone(),
+        last_move: None,
+        // the light theme has a transparent background, which GIFs don't handle well
+        theme: ThemeKind::Dark,
+    };
+
+    if options.format != "png" && options.format != "gif" {
+        return Err(format!("Unknown format: {}", options.format));
+    }
+
+    let mut rest = args[3..].iter();
+    while let Some(flag) = rest.next() {
+        let value = rest.next().ok_or_else(|| format!("{} needs a value", flag))?;
+        match flag.as_str() {
+            "--move" if options.format == "png" => {
+                let last_move = value.parse().map_err(|_| format!("Invalid move: {}", value))?;
+                options.last_move = Some(last_move);
+            },
+            "--theme" => {
+                options.theme = ThemeKind::from_name(value).ok_or_else(|| format!("Unknown theme: {}", value))?;
+            },
+            _ => return Err(format!("Unknown option: {}", flag)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn run(options: &Options) -> Result<(), Box<Error>> {
+    let mut replay: Replay = serde_json::from_reader(File::open(&options.replay_path)?)?;
+    // checked before anything is sized from the grid or the output file is created
+    replay.validate()?;
+    let (width, height) = preferred_canvas_size(&replay.grid);
+
+    if options.format == "png" {
+        if let Some(last_move) = options.last_move {
+            replay.moves.truncate(last_move);
+        }
+
+        // a single frame is drawn without any effects, the events are
+        // still needed to highlight where the snake died
+        let mut renderer = SnakeGameRenderer::new(
+            ThemeHandle::new(options.theme),
+            EffectSettings { enabled: false, screen_shake: false },
+        );
+        let game = replay.play(|_, events| {
+            for event in events {
+                renderer.handle_event(event, 0.0);
+            }
+        })?;
+        let mut target = RasterTarget::new(width, height);
+        draw(&renderer, &mut target, &game, 1.0);
+        write_png(&options.output_path, &target)
+    } else {
+        write_gif(&options.output_path, &replay, options.theme, width, height)
+    }
+}
+
+fn draw(renderer: &SnakeGameRenderer, target: &mut RasterTarget, game: &Game, progress: f32) {
+    let canvas_size = (target.width() as f32, target.height() as f32);
+    renderer.render(target, canvas_size, &RenderState { interval_progress: progress, ..RenderState::of(game) });
+}
+
+fn write_png(path: &str, target: &RasterTarget) -> Result<(), Box<Error>> {
+    use png::HasParameters;
+
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), target.width(), target.height());
+    encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&target.to_rgba())?;
+    Ok(())
+}
+
+fn write_gif(path: &str, replay: &Replay, theme: ThemeKind, width: u32, height: u32) -> Result<(), Box<Error>> {
+    use gif::SetParameter;
+
+    if width > u16::max_value() as u32 || height > u16::max_value() as u32 {
+        return Err("The grid is too large for a GIF".into());
+    }
+
+    let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), width as u16, height as u16, &[])?;
+    encoder.set(gif::Repeat::Infinite)?;
+
+    let mut renderer = SnakeGameRenderer::new(ThemeHandle::new(theme), EffectSettings::default());
+    let frame_delay = GIF_MOVE_DELAY / GIF_FRAMES_PER_MOVE;
+    // effects are timed in seconds, which advance by the delay of every frame
+    let mut time = 0.0;
+    // frames are written as they're drawn since long replays have a lot of them,
+    // `play()` can't stop early so the first error is kept until it's done
+    let mut written = Ok(());
+
+    let game = replay.play(|game, events| {
+        for event in events {
+            renderer.handle_event(event, time);
+        }
+
+        for frame in 0..GIF_FRAMES_PER_MOVE {
+            renderer.update(time);
+            let progress = (frame + 1) as f32 / GIF_FRAMES_PER_MOVE as f32;
+            if written.is_ok() {
+                written = encoder.write_frame(&gif_frame(&renderer, game, progress, width, height, frame_delay));
+            }
+            time += frame_delay as f64 / 100.0;
+        }
+    })?;
+    written?;
+
+    for _ in 0..GIF_END_FRAMES {
+        renderer.update(time);
+        encoder.write_frame(&gif_frame(&renderer, &game, 1.0, width, height, frame_delay))?;
+        time += frame_delay as f64 / 100.0;
+    }
+
+    Ok(())
+}
+
+fn gif_frame(
+    renderer: &SnakeGameRenderer,
+    game: &Game,
+    progress: f32,
+    width: u32,
+    height: u32,
+    delay: u16,
+) -> gif::Frame<'static>
+{
+    let mut target = RasterTarget::new(width, height);
+    draw(renderer, &mut target, game, progress);
+
+    let mut frame = gif::Frame::from_rgba(width as u16, height as u16, &mut target.to_rgba());
+    frame.delay = delay;
+    frame
+}
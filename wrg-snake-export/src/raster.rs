@@ -0,0 +1,99 @@
+use wrg_snake_render::RenderTarget;
+
+/// Draws into an RGBA image in memory, blending each rect over what was
+/// already drawn like the canvas does
+pub struct RasterTarget {
+    width: u32,
+    height: u32,
+    /// Rows from the bottom up, since the renderer's origin is the bottom-left
+    pixels: Vec<u8>,
+}
+
+impl RasterTarget {
+    pub fn new(width: u32, height: u32) -> RasterTarget {
+        RasterTarget {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The image as RGBA rows from the top down, which is what image formats expect
+    pub fn to_rgba(&self) -> Vec<u8> {
+        let row_len = (self.width * 4) as usize;
+        self.pixels.chunks(row_len).rev().flat_map(|row| row.iter().cloned()).collect()
+    }
+
+    /// Range of pixels whose centers are inside `start..start + size`
+    fn covered(start: f32, size: f32, max: u32) -> (u32, u32) {
+        let first = (start - 0.5).ceil().max(0.0).min(max as f32);
+        let end = (start + size - 0.5).ceil().max(0.0).min(max as f32);
+        (first as u32, end as u32)
+    }
+}
+
+impl RenderTarget for RasterTarget {
+    fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: [u8; 4]) {
+        let (first_x, end_x) = Self::covered(x, width, self.width);
+        let (first_y, end_y) = Self::covered(y, height, self.height);
+        let source_alpha = color[3] as f32 / 255.0;
+
+        for pixel_y in first_y..end_y {
+            for pixel_x in first_x..end_x {
+                let index = ((pixel_y * self.width + pixel_x) * 4) as usize;
+                let pixel = &mut self.pixels[index..index + 4];
+
+                // "source over" blending with straight alpha
+                let dest_alpha = pixel[3] as f32 / 255.0;
+                let alpha = source_alpha + dest_alpha * (1.0 - source_alpha);
+                if alpha <= 0.0 {
+                    continue;
+                }
+
+                for channel in 0..3 {
+                    let blended = (color[channel] as f32 * source_alpha
+                        + pixel[channel] as f32 * dest_alpha * (1.0 - source_alpha)) / alpha;
+                    pixel[channel] = blended.round() as u8;
+                }
+                pixel[3] = (alpha * 255.0).round() as u8;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_covered_pixels_with_the_origin_at_the_bottom() {
+        let mut target = RasterTarget::new(3, 2);
+        target.draw_rect(0.0, 0.0, 3.0, 2.0, [0, 0, 0, 255]);
+        target.draw_rect(1.0, 0.0, 2.4, 1.0, [255, 0, 0, 255]);
+
+        let rgba = target.to_rgba();
+        // top row untouched
+        assert_eq!(&rgba[0..12], &[0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255]);
+        // bottom row, the rect covers the centers of the last two pixels
+        assert_eq!(&rgba[12..24], &[0, 0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn blends_transparent_colors() {
+        let mut target = RasterTarget::new(1, 1);
+        target.draw_rect(0.0, 0.0, 1.0, 1.0, [0, 0, 0, 0]);
+        assert_eq!(target.to_rgba(), vec![0, 0, 0, 0]);
+
+        target.draw_rect(0.0, 0.0, 1.0, 1.0, [200, 100, 0, 255]);
+        target.draw_rect(0.0, 0.0, 1.0, 1.0, [0, 100, 200, 128]);
+        assert_eq!(target.to_rgba(), vec![100, 100, 100, 255]);
+    }
+}
@@ -0,0 +1,214 @@
+use std::f32::consts::PI;
+use super::{GameEvent, GridLayout, RenderTarget};
+use wrg_2d::IntVector2;
+
+const FOOD_BURST_DURATION : f64 = 0.45;
+const FOOD_BURST_PARTICLES : usize = 8;
+/// How far the particles travel, in cells
+const FOOD_BURST_DISTANCE : f32 = 1.2;
+const FOOD_BURST_PARTICLE_SIZE : f32 = 0.3;
+
+const HEAD_FLASH_DURATION : f64 = 0.25;
+
+const DEATH_DISSOLVE_DELAY : f64 = 0.15;
+const DEATH_DISSOLVE_DURATION : f64 = 0.8;
+
+const SCREEN_SHAKE_DURATION : f64 = 0.35;
+/// Largest offset of the screen shake, in cells
+const SCREEN_SHAKE_MAGNITUDE : f32 = 0.25;
+const SCREEN_SHAKE_FREQUENCY : f32 = 30.0;
+
+/// Which visual effects are played, players sensitive to motion or
+/// flashing can turn these off
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EffectSettings {
+    pub enabled: bool,
+    pub screen_shake: bool,
+}
+
+impl Default for EffectSettings {
+    fn default() -> EffectSettings {
+        EffectSettings {
+            enabled: true,
+            screen_shake: true,
+        }
+    }
+}
+
+struct FoodBurst {
+    position: IntVector2,
+    start: f64,
+}
+
+/// Short-lived animations triggered by `GameEvent`s, timed by the game clock
+pub struct Effects {
+    settings: EffectSettings,
+    /// Time of the last `update()`
+    now: f64,
+
+    food_bursts: Vec<FoodBurst>,
+    head_flash_start: Option<f64>,
+    death_start: Option<f64>,
+}
+
+impl Effects {
+    pub fn new(settings: EffectSettings) -> Effects {
+        Effects {
+            settings,
+            now: 0.0,
+            food_bursts: Vec::new(),
+            head_flash_start: None,
+            death_start: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &GameEvent, time: f64) {
+        if !self.settings.enabled {
+            return;
+        }
+
+        match *event {
+            GameEvent::FoodEaten { position } => {
+                self.food_bursts.push(FoodBurst { position, start: time });
+                self.head_flash_start = Some(time);
+            },
+            GameEvent::TailCut { .. } => {
+                self.head_flash_start = Some(time);
+            },
+            GameEvent::Died { .. } => {
+                self.death_start = Some(time);
+            },
+        }
+    }
+
+    pub fn update(&mut self, now: f64) {
+        self.now = now;
+        self.food_bursts.retain(|burst| now - burst.start < FOOD_BURST_DURATION);
+    }
+
+    /// How much (0.0 - 1.0) the head should be tinted, peaks right after eating
+    pub fn head_flash(&self) -> f32 {
+        match self.head_flash_start {
+            Some(start) => 1.0 - Self::progress(self.now, start, HEAD_FLASH_DURATION),
+            None => 0.0,
+        }
+    }
+
+    /// How far (0.0 - 1.0) the snake has dissolved after dying
+    pub fn dissolve(&self) -> f32 {
+        match self.death_start {
+            Some(start) => Self::progress(self.now, start + DEATH_DISSOLVE_DELAY, DEATH_DISSOLVE_DURATION),
+            None => 0.0,
+        }
+    }
+
+    /// Offset in pixels to shift the whole grid by
+    pub fn screen_shake(&self, layout: &GridLayout) -> (f32, f32) {
+        let start = match self.death_start {
+            Some(start) if self.settings.screen_shake => start,
+            _ => return (0.0, 0.0),
+        };
+
+        let progress = Self::progress(self.now, start, SCREEN_SHAKE_DURATION);
+        if progress >= 1.0 {
+            return (0.0, 0.0);
+        }
+
+        let magnitude = SCREEN_SHAKE_MAGNITUDE * layout.cell_size * (1.0 - progress);
+        let elapsed = (self.now - start) as f32;
+        (
+            magnitude * (elapsed * SCREEN_SHAKE_FREQUENCY).sin(),
+            magnitude * (elapsed * SCREEN_SHAKE_FREQUENCY * 1.3).cos(),
+        )
+    }
+
+    pub fn draw_particles<T: RenderTarget>(&self, target: &mut T, layout: &GridLayout, color: [u8; 4]) {
+        for burst in &self.food_bursts {
+            let progress = ease_out(Self::progress(self.now, burst.start, FOOD_BURST_DURATION));
+            let (cell_x, cell_y) = layout.cell_origin(burst.position.x as f32, burst.position.y as f32);
+            let center_x = cell_x + layout.cell_size / 2.0;
+            let center_y = cell_y + layout.cell_size / 2.0;
+
+            let distance = FOOD_BURST_DISTANCE * layout.cell_stride() * progress;
+            let size = FOOD_BURST_PARTICLE_SIZE * layout.cell_size * (1.0 - progress);
+            let mut color = color;
+            color[3] = (color[3] as f32 * (1.0 - progress)) as u8;
+
+            for i in 0..FOOD_BURST_PARTICLES {
+                let angle = (i as f32 / FOOD_BURST_PARTICLES as f32) * 2.0 * PI;
+                let x = center_x + angle.cos() * distance - size / 2.0;
+                let y = center_y + angle.sin() * distance - size / 2.0;
+                target.draw_rect(x, y, size, size, color);
+            }
+        }
+    }
+
+    fn progress(now: f64, start: f64, duration: f64) -> f32 {
+        ((now - start) / duration).max(0.0).min(1.0) as f32
+    }
+}
+
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+/// Blends from color `a` to `b` by `t` (0.0 - 1.0)
+pub fn lerp_color(a: [u8; 4], b: [u8; 4], t: f32) -> [u8; 4] {
+    let mut color = a;
+    for i in 0..4 {
+        color[i] = (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t).round() as u8;
+    }
+    color
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wrg_snake_core::DeathCause;
+
+    fn died(position: IntVector2) -> GameEvent {
+        GameEvent::Died { position, cause: DeathCause::SelfCollision { position } }
+    }
+
+    #[test]
+    fn effects_finish_after_their_duration() {
+        let mut effects = Effects::new(EffectSettings::default());
+        effects.handle_event(&GameEvent::FoodEaten { position: IntVector2 { x: 1, y: 1 } }, 1.0);
+        effects.update(1.0);
+        assert_eq!(effects.food_bursts.len(), 1);
+        assert_eq!(effects.head_flash(), 1.0);
+
+        effects.update(1.5);
+        assert!(effects.food_bursts.is_empty());
+        assert_eq!(effects.head_flash(), 0.0);
+        assert_eq!(effects.dissolve(), 0.0);
+
+        effects.handle_event(&died(IntVector2 { x: 1, y: 2 }), 2.0);
+        effects.update(2.0);
+        assert_eq!(effects.dissolve(), 0.0);
+        effects.update(3.0);
+        assert_eq!(effects.dissolve(), 1.0);
+    }
+
+    #[test]
+    fn disabled_effects_ignore_events() {
+        let mut effects = Effects::new(EffectSettings { enabled: false, screen_shake: true });
+        effects.handle_event(&GameEvent::FoodEaten { position: IntVector2 { x: 1, y: 1 } }, 1.0);
+        effects.handle_event(&died(IntVector2 { x: 1, y: 2 }), 1.0);
+        effects.update(1.1);
+
+        let layout = GridLayout::fit(&::wrg_2d::Grid::new(5, 5), 100.0, 100.0);
+        assert!(effects.food_bursts.is_empty());
+        assert_eq!(effects.head_flash(), 0.0);
+        assert_eq!(effects.dissolve(), 0.0);
+        assert_eq!(effects.screen_shake(&layout), (0.0, 0.0));
+    }
+
+    #[test]
+    fn lerp_color_blends_channels() {
+        assert_eq!(lerp_color([0, 100, 200, 255], [100, 100, 0, 255], 0.0), [0, 100, 200, 255]);
+        assert_eq!(lerp_color([0, 100, 200, 255], [100, 100, 0, 255], 0.5), [50, 100, 100, 255]);
+        assert_eq!(lerp_color([0, 100, 200, 255], [100, 100, 0, 255], 1.0), [100, 100, 0, 255]);
+    }
+}
@@ -0,0 +1,137 @@
+use super::Grid;
+
+/// Space between cells, relative to the size of a cell
+const CELL_PADDING_RATIO : f32 = 0.1;
+/// Space between the grid and the edge of the canvas, relative to the size of a cell
+const GRID_PADDING_RATIO : f32 = 0.5;
+
+/// Cell size used when picking a canvas size for a grid
+const PREFERRED_CELL_SIZE : f32 = 20.0;
+/// Keeps large grids from asking for a canvas bigger than most screens
+const MAX_PREFERRED_CANVAS_SIZE : f32 = 660.0;
+
+/// Where and how large the cells of a grid are drawn on the canvas, in pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLayout {
+    /// Bottom-left corner of the cell at (0, 0)
+    pub origin_x: f32,
+    pub origin_y: f32,
+    pub cell_size: f32,
+    pub cell_padding: f32,
+}
+
+impl GridLayout {
+    /// Scales the grid to the largest size that fits in the canvas while keeping cells
+    /// square, centering it along the axis with space left over (letterboxing)
+    pub fn fit(grid: &Grid, canvas_width: f32, canvas_height: f32) -> GridLayout {
+        let (width_units, height_units) = Self::size_in_cells(grid);
+        let cell_size = (canvas_width / width_units).min(canvas_height / height_units).max(0.0);
+
+        let cell_padding = cell_size * CELL_PADDING_RATIO;
+        let grid_padding = cell_size * GRID_PADDING_RATIO;
+        GridLayout {
+            origin_x: (canvas_width - width_units * cell_size) / 2.0 + grid_padding,
+            origin_y: (canvas_height - height_units * cell_size) / 2.0 + grid_padding,
+            cell_size,
+            cell_padding,
+        }
+    }
+
+    /// Distance between the start of one cell and the start of the next
+    pub fn cell_stride(&self) -> f32 {
+        self.cell_size + self.cell_padding
+    }
+
+    /// Size of the area covered by the cells of `grid`, not including the padding around the grid
+    pub fn grid_size(&self, grid: &Grid) -> (f32, f32) {
+        (
+            grid.width as f32 * self.cell_stride() - self.cell_padding,
+            grid.height as f32 * self.cell_stride() - self.cell_padding,
+        )
+    }
+
+    /// Bottom-left corner of the cell at a (possibly fractional) grid position
+    pub fn cell_origin(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.origin_x + x * self.cell_stride(),
+            self.origin_y + y * self.cell_stride(),
+        )
+    }
+
+    /// Width and height of the whole grid including its padding, in cells
+    fn size_in_cells(grid: &Grid) -> (f32, f32) {
+        let padding = 2.0 * GRID_PADDING_RATIO - CELL_PADDING_RATIO;
+        (
+            grid.width as f32 * (1.0 + CELL_PADDING_RATIO) + padding,
+            grid.height as f32 * (1.0 + CELL_PADDING_RATIO) + padding,
+        )
+    }
+}
+
+/// Canvas size to start with for a grid, the layout adapts if the canvas is resized later
+pub fn preferred_canvas_size(grid: &Grid) -> (u32, u32) {
+    let (width_units, height_units) = GridLayout::size_in_cells(grid);
+    let cell_size = PREFERRED_CELL_SIZE
+        .min(MAX_PREFERRED_CANVAS_SIZE / width_units)
+        .min(MAX_PREFERRED_CANVAS_SIZE / height_units);
+
+    ((width_units * cell_size).round() as u32, (height_units * cell_size).round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 0.001, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn preferred_size_matches_fixed_cell_size() {
+        // 15 cells of 20px with 2px between them, and 10px around the grid
+        assert_eq!(preferred_canvas_size(&Grid::new(15, 15)), (348, 348));
+        assert_eq!(preferred_canvas_size(&Grid::new(10, 5)), (238, 128));
+    }
+
+    #[test]
+    fn preferred_size_is_capped_for_large_grids() {
+        let (width, height) = preferred_canvas_size(&Grid::new(100, 50));
+        assert_eq!(width, 660);
+        assert!(height < 660);
+    }
+
+    #[test]
+    fn fits_exactly_into_preferred_size() {
+        let grid = Grid::new(15, 15);
+        let layout = GridLayout::fit(&grid, 348.0, 348.0);
+        assert_close(layout.cell_size, 20.0);
+        assert_close(layout.cell_padding, 2.0);
+        assert_close(layout.origin_x, 10.0);
+        assert_close(layout.origin_y, 10.0);
+
+        let (grid_width, grid_height) = layout.grid_size(&grid);
+        assert_close(grid_width, 328.0);
+        assert_close(grid_height, 328.0);
+    }
+
+    #[test]
+    fn letterboxes_to_keep_cells_square() {
+        let grid = Grid::new(10, 5);
+
+        // wide canvas, limited by height so centered horizontally
+        let layout = GridLayout::fit(&grid, 1000.0, 128.0);
+        assert_close(layout.cell_size, 20.0);
+        assert_close(layout.origin_y, 10.0);
+        assert_close(layout.origin_x, (1000.0 - 238.0) / 2.0 + 10.0);
+
+        // tall canvas, limited by width so centered vertically
+        let layout = GridLayout::fit(&grid, 119.0, 1000.0);
+        assert_close(layout.cell_size, 10.0);
+        assert_close(layout.origin_x, 5.0);
+        assert_close(layout.origin_y, (1000.0 - 64.0) / 2.0 + 5.0);
+
+        let (x, y) = layout.cell_origin(2.0, 0.5);
+        assert_close(x, 5.0 + 2.0 * 11.0);
+        assert_close(y, layout.origin_y + 5.5);
+    }
+}
@@ -0,0 +1,341 @@
+//! Draws games of snake into any `RenderTarget`, shared by the wasm
+//! application and the native tools.
+#[macro_use]
+extern crate serde;
+extern crate wrg_2d;
+extern crate wrg_snake_core;
+
+use wrg_2d::{Grid, IntVector2};
+use wrg_snake_core::{GameEvent, Snake};
+
+mod effects;
+mod layout;
+mod snake;
+mod state;
+mod target;
+mod theme;
+
+pub use effects::EffectSettings;
+pub use layout::{GridLayout, preferred_canvas_size};
+pub use state::RenderState;
+pub use target::{RenderTarget, RecordingTarget, RecordedRect};
+pub use theme::{Theme, ThemeHandle, ThemeKind};
+
+use effects::Effects;
+
+pub struct SnakeGameRenderer {
+    theme: ThemeHandle,
+    effects: Effects,
+    show_grid_markers: bool,
+    /// Where the snake died, highlighted until the renderer is dropped
+    fatal_cell: Option<IntVector2>,
+}
+
+impl SnakeGameRenderer {
+    pub fn new(theme: ThemeHandle, effect_settings: EffectSettings) -> SnakeGameRenderer {
+        SnakeGameRenderer {
+            theme,
+            effects: Effects::new(effect_settings),
+            show_grid_markers: true,
+            fatal_cell: None,
+        }
+    }
+
+    /// Whether to draw a marker in every empty cell of the grid, they're drawn by default
+    pub fn with_grid_markers(mut self, show_grid_markers: bool) -> SnakeGameRenderer {
+        self.show_grid_markers = show_grid_markers;
+        self
+    }
+
+    /// Starts any effects for `event`, which happened at `time`
+    pub fn handle_event(&mut self, event: &GameEvent, time: f64) {
+        // the fatal cell isn't an effect, so it's shown even with effects turned off
+        if let GameEvent::Died { cause, .. } = *event {
+            self.fatal_cell = cause.position();
+        }
+        self.effects.handle_event(event, time);
+    }
+
+    /// Advances the effects to the current time, should be called every tick
+    pub fn update(&mut self, now: f64) {
+        self.effects.update(now);
+    }
+
+    /// Draws the game into `target`, scaled to fit a canvas of `canvas_size` pixels
+    pub fn render<T: RenderTarget>(&self, target: &mut T, canvas_size: (f32, f32), state: &RenderState) {
+        let theme = self.theme.theme();
+        let grid = state.grid;
+
+        // The canvas can be resized by the host at any time, so the layout
+        // is recalculated every frame
+        let (canvas_width, canvas_height) = canvas_size;
+        let mut layout = GridLayout::fit(grid, canvas_width, canvas_height);
+        let (shake_x, shake_y) = self.effects.screen_shake(&layout);
+        layout.origin_x += shake_x;
+        layout.origin_y += shake_y;
+
+        // Draw the background over the whole canvas, including any letterboxing
+        target.draw_rect(0.0, 0.0, canvas_width, canvas_height, theme.background);
+
+        // Draw the grid markers
+        if self.show_grid_markers {
+            for x in 0..grid.width {
+                for y in 0..grid.height {
+                    Self::draw_cell(target, &layout, x, y, theme.grid_marker);
+                }
+            }
+        }
+
+        // Draw the portals, both ends of a portal in the same color
+        for (index, portal) in state.level.portals.iter().enumerate() {
+            let color = theme.portals[index % theme.portals.len()];
+            for position in &[portal.a, portal.b] {
+                Self::draw_cell(target, &layout, position.x as u16, position.y as u16, color);
+            }
+        }
+
+        // Draw the foods
+        for food in state.foods {
+            let pos = food.pos();
+            let color = if food.is_bonus() { theme.bonus_food } else { theme.food };
+            Self::draw_cell(target, &layout, pos.x as u16, pos.y as u16, color);
+        }
+
+        // Draw the hazards over the food they move across
+        for cell in &state.hazards {
+            let color = if cell.active { theme.hazard } else { theme.hazard_inactive };
+            Self::draw_cell(target, &layout, cell.position.x as u16, cell.position.y as u16, color);
+        }
+
+        snake::draw_snake(target, &layout, &theme, &self.effects, state);
+
+        self.effects.draw_particles(target, &layout, theme.food);
+
+        if let Some(position) = self.fatal_cell {
+            Self::draw_cell(target, &layout, position.x as u16, position.y as u16, theme.fatal_cell);
+        }
+    }
+
+    /// Draws a rect at `origin` with `size`, given in pixels relative to the bottom-left of the grid,
+    /// wrapping any part that goes over the edges to the opposite side and clipping it to the grid
+    fn draw_wrapped_rect<T: RenderTarget>(
+        target: &mut T,
+        grid: &Grid,
+        layout: &GridLayout,
+        origin: (f32, f32),
+        size: (f32, f32),
+        color: [u8; 4],
+    ) {
+        let ((x, y), (width, height)) = (origin, size);
+        let (max_x, max_y) = layout.grid_size(grid);
+        let wrap_width = max_x + layout.cell_padding;
+        let wrap_height = max_y + layout.cell_padding;
+
+        for &wrap_x in &[-wrap_width, 0.0, wrap_width] {
+            for &wrap_y in &[-wrap_height, 0.0, wrap_height] {
+                let left = (x + wrap_x).max(0.0);
+                let right = (x + wrap_x + width).min(max_x);
+                let bottom = (y + wrap_y).max(0.0);
+                let top = (y + wrap_y + height).min(max_y);
+                if right <= left || top <= bottom {
+                    continue;
+                }
+
+                target.draw_rect(layout.origin_x + left, layout.origin_y + bottom, right - left, top - bottom, color);
+            }
+        }
+    }
+
+    fn draw_cell<T: RenderTarget>(target: &mut T, layout: &GridLayout, x: u16, y: u16, color: [u8; 4]) {
+        let (pos_x, pos_y) = layout.cell_origin(x as f32, y as f32);
+        target.draw_rect(pos_x, pos_y, layout.cell_size, layout.cell_size, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use super::*;
+    use wrg_2d::Direction;
+    use wrg_snake_core::{DeathCause, Food, Hazard, Level, Portal};
+
+    /// Compares what was drawn against `golden/<name>.txt`, run with
+    /// `UPDATE_GOLDENS=1` to write the current output as the new golden file
+    fn assert_golden(name: &str, target: &RecordingTarget) {
+        let path: PathBuf = [
+            env!("CARGO_MANIFEST_DIR"), "src", "golden",
+            &format!("{}.txt", name),
+        ].iter().collect();
+
+        let actual = target.to_string();
+        if env::var("UPDATE_GOLDENS").is_ok() {
+            fs::write(&path, &actual).expect("Failed to write golden file");
+            return;
+        }
+
+        let expected = fs::read_to_string(&path).expect("Missing golden file, run with UPDATE_GOLDENS=1");
+        assert!(actual == expected, "{} differs from the golden file:\n{}", name, actual);
+    }
+
+    fn render(grid: &Grid, snake: &Snake, direction: Direction, progress: f32, foods: &Vec<Food>) -> RecordingTarget {
+        render_level(grid, &Level::default(), snake, direction, progress, foods)
+    }
+
+    fn render_level(
+        grid: &Grid,
+        level: &Level,
+        snake: &Snake,
+        direction: Direction,
+        progress: f32,
+        foods: &Vec<Food>,
+    ) -> RecordingTarget {
+        let renderer = SnakeGameRenderer::new(ThemeHandle::new(ThemeKind::Light), EffectSettings::default());
+        let mut target = RecordingTarget::new();
+        // 3 cells + padding fits exactly into 42px with 10px cells
+        renderer.render(&mut target, (42.0, 42.0), &RenderState {
+            grid,
+            level,
+            hazards: level.hazard_cells(grid, 0),
+            snake,
+            direction,
+            interval_progress: progress,
+            foods,
+        });
+        target
+    }
+
+    /// Just the snake, facing right at the end of an interval
+    fn still_snake<'a>(grid: &'a Grid, level: &'a Level, snake: &'a Snake) -> RenderState<'a> {
+        RenderState {
+            grid,
+            level,
+            hazards: Vec::new(),
+            snake,
+            direction: Direction::Right,
+            interval_progress: 1.0,
+            foods: &[],
+        }
+    }
+
+    #[test]
+    fn single_cell_snake() {
+        let grid = Grid::new(3, 3);
+        let snake = Snake::new(IntVector2 { x: 1, y: 1 }, 1, grid.area()).unwrap();
+        let target = render(&grid, &snake, Direction::Right, 1.0, &vec![]);
+        assert_golden("single_cell_snake", &target);
+    }
+
+    #[test]
+    fn snake_and_food_mid_interval() {
+        let grid = Grid::new(3, 3);
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 0 }, 3, grid.area()).unwrap();
+        snake.move_to(IntVector2 { x: 1, y: 0 });
+        snake.move_to(IntVector2 { x: 1, y: 1 });
+        snake.move_to(IntVector2 { x: 1, y: 2 });
+
+        let foods = vec![Food::new(IntVector2 { x: 2, y: 2 })];
+        let target = render(&grid, &snake, Direction::Up, 0.5, &foods);
+        assert_golden("snake_and_food_mid_interval", &target);
+    }
+
+    #[test]
+    fn snake_wrapping_across_edge() {
+        let grid = Grid::new(3, 3);
+        let mut snake = Snake::new(IntVector2 { x: 1, y: 1 }, 2, grid.area()).unwrap();
+        snake.move_to(IntVector2 { x: 2, y: 1 });
+        snake.move_to(IntVector2 { x: 0, y: 1 });
+
+        let target = render(&grid, &snake, Direction::Right, 0.25, &vec![]);
+        assert_golden("snake_wrapping_across_edge", &target);
+    }
+
+    #[test]
+    fn snake_through_portal() {
+        let grid = Grid::new(3, 3);
+        let level = Level {
+            portals: vec![Portal { a: IntVector2 { x: 1, y: 1 }, b: IntVector2 { x: 2, y: 2 } }],
+            ..Level::default()
+        };
+        let mut snake = Snake::new(IntVector2 { x: 0, y: 1 }, 3, grid.area()).unwrap();
+        // moved right into the portal at (1, 1), coming out at (2, 2)
+        snake.move_to(IntVector2 { x: 2, y: 2 });
+
+        let target = render_level(&grid, &level, &snake, Direction::Right, 0.5, &vec![]);
+        assert_golden("snake_through_portal", &target);
+
+        // both ends are drawn in the first portal color, and the snake isn't drawn
+        // between the ends, which would cover more than a row or column of cells
+        let theme = ThemeKind::Light.theme();
+        assert_eq!(target.rects.iter().filter(|rect| rect.color == theme.portals[0]).count(), 2);
+        let cell_size = GridLayout::fit(&grid, 42.0, 42.0).cell_size;
+        assert!(target.rects.iter()
+            .filter(|rect| rect.color == theme.snake)
+            .all(|rect| rect.width <= cell_size || rect.height <= cell_size));
+    }
+
+    #[test]
+    fn hazards_are_drawn_over_food() {
+        let grid = Grid::new(3, 3);
+        let level = Level {
+            hazards: vec![
+                Hazard::Spikes { cells: vec![IntVector2 { x: 0, y: 2 }], on: 1, off: 1 },
+                Hazard::Patrol { path: vec![IntVector2 { x: 2, y: 2 }, IntVector2 { x: 2, y: 1 }], every: 1 },
+            ],
+            ..Level::default()
+        };
+        let snake = Snake::new(IntVector2 { x: 0, y: 0 }, 1, grid.area()).unwrap();
+        let foods = vec![Food::new(IntVector2 { x: 2, y: 2 })];
+
+        let target = render_level(&grid, &level, &snake, Direction::Right, 1.0, &foods);
+        let theme = ThemeKind::Light.theme();
+        let food_index = target.rects.iter().position(|rect| rect.color == theme.food).unwrap();
+        let hazard_index = target.rects.iter().rposition(|rect| rect.color == theme.hazard).unwrap();
+        let (food, hazard) = (target.rects[food_index], target.rects[hazard_index]);
+        assert!(hazard_index > food_index);
+        assert_eq!((hazard.x, hazard.y), (food.x, food.y));
+
+        // the spikes start out active, so both hazards are drawn as dangerous
+        assert_eq!(target.rects.iter().filter(|rect| rect.color == theme.hazard).count(), 2);
+        assert_eq!(target.rects.iter().filter(|rect| rect.color == theme.hazard_inactive).count(), 0);
+    }
+
+    #[test]
+    fn grid_markers_can_be_hidden() {
+        let grid = Grid::new(3, 3);
+        let snake = Snake::new(IntVector2 { x: 1, y: 1 }, 1, grid.area()).unwrap();
+        let level = Level::default();
+        let marker_color = ThemeKind::Light.theme().grid_marker;
+        let count_markers = |renderer: SnakeGameRenderer| {
+            let mut target = RecordingTarget::new();
+            renderer.render(&mut target, (42.0, 42.0), &still_snake(&grid, &level, &snake));
+            target.rects.iter().filter(|rect| rect.color == marker_color).count()
+        };
+
+        let renderer = SnakeGameRenderer::new(ThemeHandle::new(ThemeKind::Light), EffectSettings::default());
+        assert_eq!(count_markers(renderer), 9);
+        let renderer = SnakeGameRenderer::new(ThemeHandle::new(ThemeKind::Light), EffectSettings::default())
+            .with_grid_markers(false);
+        assert_eq!(count_markers(renderer), 0);
+    }
+
+    #[test]
+    fn highlights_the_fatal_cell() {
+        let grid = Grid::new(3, 3);
+        let snake = Snake::new(IntVector2 { x: 1, y: 1 }, 1, grid.area()).unwrap();
+        let level = Level::default();
+        let mut renderer = SnakeGameRenderer::new(
+            ThemeHandle::new(ThemeKind::Light),
+            EffectSettings { enabled: false, screen_shake: false },
+        );
+        let position = IntVector2 { x: 2, y: 1 };
+        renderer.handle_event(&GameEvent::Died { position, cause: DeathCause::SelfCollision { position } }, 1.0);
+
+        let mut target = RecordingTarget::new();
+        renderer.render(&mut target, (42.0, 42.0), &still_snake(&grid, &level, &snake));
+        let last = target.rects.last().unwrap();
+        assert_eq!(last.color, ThemeKind::Light.theme().fatal_cell);
+        assert_eq!((last.x, last.y), GridLayout::fit(&grid, 42.0, 42.0).cell_origin(2.0, 1.0));
+    }
+}
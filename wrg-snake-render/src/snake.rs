@@ -0,0 +1,184 @@
+use super::{SnakeGameRenderer, GridLayout, RenderState, RenderTarget, Snake, Grid, Theme};
+use super::effects::{Effects, lerp_color};
+use wrg_2d::{Direction, IntVector2};
+
+/// Width of the very end of the tail, as a fraction of a full segment
+const TAIL_TIP_WIDTH : f32 = 0.4;
+/// How many cells (from the end of the tail) it takes to get to full width
+const TAIL_TAPER_LENGTH : f32 = 2.5;
+
+const EYE_SIZE : f32 = 0.2;
+const EYE_FORWARD_OFFSET : f32 = 0.18;
+const EYE_SIDE_OFFSET : f32 = 0.22;
+
+/// A point along the snake in (fractional) cell coordinates, each connected point
+/// is within one cell of the point before it, so points can go off the grid
+/// when the snake crosses an edge.
+#[derive(Clone, Copy)]
+struct PathPoint {
+    x: f32,
+    y: f32,
+    /// Fraction of a full segment width the snake is at this point
+    width: f32,
+    /// Whether the snake goes straight from the point before this one, instead
+    /// of jumping here through a portal
+    connected: bool,
+}
+
+/// Draws the snake as a continuous path from the head to the tail, with the head
+/// and tail interpolated by the interval progress between their previous and current cells.
+pub fn draw_snake<T: RenderTarget>(
+    target: &mut T,
+    layout: &GridLayout,
+    theme: &Theme,
+    effects: &Effects,
+    state: &RenderState,
+) {
+    let grid = state.grid;
+    let mut path = snake_path(grid, state.snake, state.interval_progress);
+
+    // after dying the snake shrinks away segment by segment, starting from the tail
+    let dissolve = effects.dissolve();
+    if dissolve > 0.0 {
+        let count = path.len() as f32;
+        for (i, point) in path.iter_mut().enumerate() {
+            let start = (count - 1.0 - i as f32) / count;
+            let shrink = ((dissolve - start) * count).max(0.0).min(1.0);
+            point.width *= 1.0 - shrink;
+        }
+    }
+
+    for pair in path.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if b.connected {
+            draw_span(target, grid, layout, a, b, a.width.min(b.width), theme.snake);
+        }
+    }
+
+    for point in path.iter().skip(1) {
+        draw_span(target, grid, layout, *point, *point, point.width, theme.snake);
+    }
+
+    let head = path[0];
+    let head_color = lerp_color(theme.snake_head, theme.food, effects.head_flash());
+    draw_span(target, grid, layout, head, head, head.width, head_color);
+    if head.width > 0.5 {
+        draw_eyes(target, grid, layout, head, state.direction, theme.snake_eye);
+    }
+}
+
+fn snake_path(grid: &Grid, snake: &Snake, progress: f32) -> Vec<PathPoint> {
+    // cells further apart than a single move were jumped between through a portal
+    let adjacent = |a: &IntVector2, b: &IntVector2| grid.torus_distance(*a, *b) <= 1;
+    let lerp = |from: IntVector2, to: IntVector2| {
+        // jumping through a portal isn't interpolated, the snake is already out the other end
+        if !adjacent(&from, &to) {
+            return (to.x as f32, to.y as f32);
+        }
+        let delta = grid.wrapped_delta(from, to);
+        (from.x as f32 + delta.x as f32 * progress, from.y as f32 + delta.y as f32 * progress)
+    };
+
+    // head is between where it was and where it is now, followed by every cell
+    // but the head, with the tail coming from where it was before the move
+    let positions: Vec<IntVector2> = snake.positions().cloned().collect();
+    let mut points = vec![(lerp(snake.previous_head_position(), snake.current_position()), true)];
+    for (i, p) in positions.iter().enumerate().skip(1) {
+        points.push(((p.x as f32, p.y as f32), adjacent(&positions[i - 1], p)));
+    }
+    if snake.length() > 1 {
+        points.push((lerp(snake.previous_tail_position(), snake.tail_position()), true));
+    }
+
+    // unwrap the points so each is next to the previous point, even across the grid edge
+    let (width, height) = (grid.width as f32, grid.height as f32);
+    for i in 1..points.len() {
+        let ((prev_x, prev_y), _) = points[i - 1];
+        let ((ref mut x, ref mut y), connected) = points[i];
+        if connected {
+            *x = prev_x + wrap_offset(*x - prev_x, width);
+            *y = prev_y + wrap_offset(*y - prev_y, height);
+        }
+    }
+
+    // the snake narrows by how far along the path each point is from the tail,
+    // going through a portal counts as a single move
+    let mut path: Vec<PathPoint> = points.iter()
+        .map(|&((x, y), connected)| PathPoint { x, y, width: 1.0, connected })
+        .collect();
+    let mut distance_from_tail = 0.0;
+    for i in (0..path.len()).rev() {
+        if i + 1 < path.len() {
+            let next = path[i + 1];
+            distance_from_tail += if next.connected {
+                (path[i].x - next.x).abs() + (path[i].y - next.y).abs()
+            } else {
+                1.0
+            };
+        }
+
+        let taper = distance_from_tail / TAIL_TAPER_LENGTH;
+        path[i].width = (TAIL_TIP_WIDTH + (1.0 - TAIL_TIP_WIDTH) * taper).min(1.0);
+    }
+
+    // the head is always full width, even on a short snake
+    path[0].width = 1.0;
+
+    path
+}
+
+/// Shifts `delta` by a multiple of `size` so that it's as close to 0 as possible
+fn wrap_offset(delta: f32, size: f32) -> f32 {
+    delta - (delta / size).round() * size
+}
+
+/// Draws a rect covering the cells at both `a` and `b` (and the padding in between),
+/// narrowed to `width` across the direction from `a` to `b`
+fn draw_span<T: RenderTarget>(
+    target: &mut T,
+    grid: &Grid,
+    layout: &GridLayout,
+    a: PathPoint,
+    b: PathPoint,
+    width: f32,
+    color: [u8; 4],
+) {
+    let cell_stride = layout.cell_stride();
+    let cell_size = layout.cell_size;
+    let inset = cell_size * (1.0 - width) / 2.0;
+
+    let horizontal = (a.x - b.x).abs() > 0.0;
+    let vertical = (a.y - b.y).abs() > 0.0;
+    let inset_x = if horizontal { 0.0 } else { inset };
+    let inset_y = if vertical { 0.0 } else { inset };
+
+    let left = a.x.min(b.x) * cell_stride + inset_x;
+    let right = a.x.max(b.x) * cell_stride + cell_size - inset_x;
+    let bottom = a.y.min(b.y) * cell_stride + inset_y;
+    let top = a.y.max(b.y) * cell_stride + cell_size - inset_y;
+    SnakeGameRenderer::draw_wrapped_rect(target, grid, layout, (left, bottom), (right - left, top - bottom), color);
+}
+
+fn draw_eyes<T: RenderTarget>(
+    target: &mut T,
+    grid: &Grid,
+    layout: &GridLayout,
+    head: PathPoint,
+    direction: Direction,
+    color: [u8; 4],
+) {
+    let cell_stride = layout.cell_stride();
+    let cell_size = layout.cell_size;
+
+    let forward = direction.to_position();
+    let side = direction.rotate_cw().to_position();
+    for &side_sign in &[-1.0, 1.0] {
+        let center_x = 0.5 + forward.x as f32 * EYE_FORWARD_OFFSET + side.x as f32 * EYE_SIDE_OFFSET * side_sign;
+        let center_y = 0.5 + forward.y as f32 * EYE_FORWARD_OFFSET + side.y as f32 * EYE_SIDE_OFFSET * side_sign;
+
+        let x = head.x * cell_stride + (center_x - EYE_SIZE / 2.0) * cell_size;
+        let y = head.y * cell_stride + (center_y - EYE_SIZE / 2.0) * cell_size;
+        let eye_size = EYE_SIZE * cell_size;
+        SnakeGameRenderer::draw_wrapped_rect(target, grid, layout, (x, y), (eye_size, eye_size), color);
+    }
+}
@@ -0,0 +1,30 @@
+use wrg_2d::{Direction, Grid};
+use wrg_snake_core::{Food, Game, HazardCell, Level, Snake};
+
+/// Everything about a game that gets drawn
+pub struct RenderState<'a> {
+    pub grid: &'a Grid,
+    pub level: &'a Level,
+    pub hazards: Vec<HazardCell>,
+    pub snake: &'a Snake,
+    /// The direction the snake last moved in, which its eyes look towards
+    pub direction: Direction,
+    /// How far along (0.0 - 1.0) the snake is between its previous and current cells
+    pub interval_progress: f32,
+    pub foods: &'a [Food],
+}
+
+impl<'a> RenderState<'a> {
+    /// The game as it is right now
+    pub fn of(game: &'a Game) -> RenderState<'a> {
+        RenderState {
+            grid: game.grid(),
+            level: game.level(),
+            hazards: game.hazard_cells(),
+            snake: game.snake(),
+            direction: game.direction(),
+            interval_progress: game.interval_progress(),
+            foods: game.foods(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+/// Something the renderer can draw into, positions are in pixels
+/// with the origin at the bottom-left
+pub trait RenderTarget {
+    fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: [u8; 4]);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordedRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub color: [u8; 4],
+}
+
+/// Records every draw call in order instead of drawing anything,
+/// so tests can inspect exactly what was drawn
+#[derive(Debug, Default)]
+pub struct RecordingTarget {
+    pub rects: Vec<RecordedRect>,
+}
+
+impl RecordingTarget {
+    pub fn new() -> RecordingTarget {
+        RecordingTarget { rects: Vec::new() }
+    }
+}
+
+impl RenderTarget for RecordingTarget {
+    fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: [u8; 4]) {
+        self.rects.push(RecordedRect { x, y, width, height, color });
+    }
+}
+
+/// One rect per line, rounded so the output is stable enough to compare against golden files
+impl fmt::Display for RecordingTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for rect in &self.rects {
+            writeln!(
+                f,
+                "rect {:.2} {:.2} {:.2} {:.2} #{:02x}{:02x}{:02x}{:02x}",
+                rect.x, rect.y, rect.width, rect.height,
+                rect.color[0], rect.color[1], rect.color[2], rect.color[3],
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Every color used to draw the game and its menus
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub background: [u8; 4],
+    pub grid_marker: [u8; 4],
+    pub food: [u8; 4],
+    /// Food worth extra, like bonus time in time attack
+    pub bonus_food: [u8; 4],
+    pub snake: [u8; 4],
+    pub snake_head: [u8; 4],
+    pub snake_eye: [u8; 4],
+    /// Drawn over the cell the snake died in
+    pub fatal_cell: [u8; 4],
+    /// Both ends of a portal share a color, picked in order for each portal
+    pub portals: [[u8; 4]; 3],
+    /// Hazards that hurt the snake right now, and ones that are safe for the moment
+    pub hazard: [u8; 4],
+    pub hazard_inactive: [u8; 4],
+
+    /// Drawn over the game when a menu is open
+    pub overlay: [u8; 4],
+    pub text: [u8; 4],
+
+    pub button: [u8; 4],
+    pub button_hovered: [u8; 4],
+    pub button_clicked: [u8; 4],
+    /// Outline around the button picked with the keyboard
+    pub button_focused: [u8; 4],
+}
+
+/// The built-in themes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeKind {
+    Light,
+    Dark,
+    HighContrast,
+    ColorblindSafe,
+}
+
+impl ThemeKind {
+    pub const ALL: [ThemeKind; 4] = [
+        ThemeKind::Light,
+        ThemeKind::Dark,
+        ThemeKind::HighContrast,
+        ThemeKind::ColorblindSafe,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeKind::Light => "Light",
+            ThemeKind::Dark => "Dark",
+            ThemeKind::HighContrast => "High Contrast",
+            ThemeKind::ColorblindSafe => "Colorblind Safe",
+        }
+    }
+
+    /// Finds a theme by its name, ignoring case, spaces and dashes so
+    /// "high-contrast" finds `HighContrast`
+    pub fn from_name(name: &str) -> Option<ThemeKind> {
+        fn normalize(name: &str) -> String {
+            name.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect()
+        }
+
+        let name = normalize(name);
+        Self::ALL.iter().cloned().find(|kind| normalize(kind.name()) == name)
+    }
+
+    /// The theme after this one, wrapping around to the first
+    pub fn next(&self) -> ThemeKind {
+        let index = Self::ALL.iter().position(|kind| kind == self).expect("ALL contains every ThemeKind");
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self {
+            ThemeKind::Light => Theme {
+                // let the page show through
+                background: [0, 0, 0, 0],
+                grid_marker: [235, 207, 178, 80],
+                food: [179, 141, 151, 255],
+                bonus_food: [222, 170, 92, 255],
+                snake: [66, 75, 84, 255],
+                snake_head: [45, 52, 59, 255],
+                snake_eye: [245, 239, 232, 255],
+                fatal_cell: [214, 69, 65, 200],
+                portals: [[110, 158, 199, 255], [150, 120, 180, 255], [120, 170, 130, 255]],
+                hazard: [150, 60, 50, 255],
+                hazard_inactive: [150, 60, 50, 90],
+
+                overlay: [255, 255, 255, 150],
+                text: [66, 75, 84, 255],
+
+                button: [190, 176, 161, 255],
+                button_hovered: [187, 173, 158, 255],
+                button_clicked: [175, 161, 146, 255],
+                button_focused: [66, 75, 84, 255],
+            },
+            ThemeKind::Dark => Theme {
+                background: [40, 44, 52, 255],
+                grid_marker: [80, 86, 99, 90],
+                food: [224, 108, 117, 255],
+                bonus_food: [198, 120, 221, 255],
+                snake: [152, 195, 121, 255],
+                snake_head: [122, 170, 92, 255],
+                snake_eye: [40, 44, 52, 255],
+                fatal_cell: [229, 192, 123, 220],
+                portals: [[97, 175, 239, 255], [86, 182, 194, 255], [209, 154, 102, 255]],
+                hazard: [190, 80, 70, 255],
+                hazard_inactive: [190, 80, 70, 80],
+
+                overlay: [30, 33, 39, 170],
+                text: [220, 223, 228, 255],
+
+                button: [62, 68, 81, 255],
+                button_hovered: [72, 79, 94, 255],
+                button_clicked: [52, 57, 68, 255],
+                button_focused: [97, 175, 239, 255],
+            },
+            ThemeKind::HighContrast => Theme {
+                background: [0, 0, 0, 255],
+                grid_marker: [70, 70, 70, 255],
+                food: [255, 255, 0, 255],
+                bonus_food: [0, 255, 0, 255],
+                snake: [255, 255, 255, 255],
+                snake_head: [0, 255, 255, 255],
+                snake_eye: [0, 0, 0, 255],
+                fatal_cell: [255, 0, 255, 255],
+                portals: [[0, 128, 255, 255], [255, 128, 0, 255], [255, 0, 0, 255]],
+                hazard: [255, 0, 0, 255],
+                hazard_inactive: [128, 0, 0, 255],
+
+                overlay: [0, 0, 0, 190],
+                text: [255, 255, 255, 255],
+
+                button: [40, 40, 40, 255],
+                button_hovered: [90, 90, 90, 255],
+                button_clicked: [130, 130, 130, 255],
+                button_focused: [255, 255, 0, 255],
+            },
+            // Based on the Okabe-Ito palette, which stays distinguishable
+            // for the common forms of color blindness
+            ThemeKind::ColorblindSafe => Theme {
+                background: [250, 250, 250, 255],
+                grid_marker: [200, 200, 200, 120],
+                food: [230, 159, 0, 255],
+                bonus_food: [0, 158, 115, 255],
+                snake: [0, 114, 178, 255],
+                snake_head: [0, 84, 138, 255],
+                snake_eye: [255, 255, 255, 255],
+                fatal_cell: [204, 121, 167, 230],
+                portals: [[86, 180, 233, 255], [213, 94, 0, 255], [240, 228, 66, 255]],
+                hazard: [0, 0, 0, 255],
+                hazard_inactive: [0, 0, 0, 70],
+
+                overlay: [255, 255, 255, 150],
+                text: [34, 34, 34, 255],
+
+                button: [86, 180, 233, 255],
+                button_hovered: [76, 165, 215, 255],
+                button_clicked: [60, 145, 195, 255],
+                button_focused: [213, 94, 0, 255],
+            },
+        }
+    }
+}
+
+/// Shared handle to the currently selected theme, so switching themes
+/// is picked up by everything holding a clone of the handle
+#[derive(Clone)]
+pub struct ThemeHandle {
+    kind: Rc<Cell<ThemeKind>>,
+}
+
+impl ThemeHandle {
+    pub fn new(kind: ThemeKind) -> ThemeHandle {
+        ThemeHandle { kind: Rc::new(Cell::new(kind)) }
+    }
+
+    pub fn kind(&self) -> ThemeKind {
+        self.kind.get()
+    }
+
+    pub fn set_kind(&self, kind: ThemeKind) {
+        self.kind.set(kind);
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.kind().theme()
+    }
+}
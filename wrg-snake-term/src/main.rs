@@ -0,0 +1,171 @@
+//! Plays snake in the terminal, useful for trying out rule changes
+//! without building the wasm application.
+//!
+//! Usage: `wrg-snake-term [WIDTH HEIGHT] [--record FILE]`
+extern crate serde_json;
+extern crate termion;
+extern crate wrg_2d;
+extern crate wrg_snake_core;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, Write};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use termion::{async_stdin, clear, cursor};
+use termion::event::Key;
+use termion::input::TermRead;
+use termion::raw::IntoRawMode;
+use wrg_2d::{Direction, Grid, IntVector2};
+use wrg_snake_core::{Game, GameState, Replay, XorShiftRandom};
+
+mod text;
+
+const DEFAULT_GRID_SIZE: u16 = 15;
+const START_LENGTH: usize = 3;
+const FRAME_TIME_MS: u64 = 16;
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let record_path = match args.iter().position(|arg| arg == "--record") {
+        Some(index) => {
+            if index + 1 >= args.len() {
+                exit_with_usage("--record needs a file to save replays to");
+            }
+            let path = args.remove(index + 1);
+            args.remove(index);
+            Some(path)
+        },
+        None => None,
+    };
+
+    let grid = match parse_grid(args) {
+        Ok(grid) => grid,
+        Err(message) => exit_with_usage(&message),
+    };
+
+    if let Err(err) = run(grid, record_path) {
+        eprintln!("Failed to run the game: {}", err);
+        process::exit(1);
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}\nUsage: wrg-snake-term [WIDTH HEIGHT] [--record FILE]", message);
+    process::exit(1);
+}
+
+fn parse_grid(args: Vec<String>) -> Result<Grid, String> {
+    let (width, height) = match args.len() {
+        0 => (DEFAULT_GRID_SIZE, DEFAULT_GRID_SIZE),
+        2 => {
+            let parse = |arg: &str| arg.parse::<u16>().map_err(|_| format!("Invalid grid size: {}", arg));
+            (parse(&args[0])?, parse(&args[1])?)
+        },
+        _ => return Err("Expected either no arguments or a width and height".to_string()),
+    };
+
+    let grid = Grid::new(width, height);
+    if grid.area() < START_LENGTH {
+        return Err(format!("The grid needs at least {} cells", START_LENGTH));
+    }
+
+    Ok(grid)
+}
+
+fn start_position(grid: &Grid) -> IntVector2 {
+    IntVector2 { x: 0, y: grid.height as i32 / 2 }
+}
+
+fn new_game(grid: Grid) -> Game {
+    Game::new(grid, start_position(&grid), START_LENGTH, Direction::Right, true)
+        .expect("Grid is checked to fit the snake")
+}
+
+fn random_seed() -> u64 {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or(Duration::from_secs(0));
+    since_epoch.as_secs() ^ since_epoch.subsec_nanos() as u64
+}
+
+/// Saves `game` as a replay which can be exported with `wrg-snake-export`
+fn save_replay(path: &str, game: &Game, seed: u64) -> io::Result<()> {
+    let grid = *game.grid();
+    let replay = Replay {
+        grid,
+        start_position: start_position(&grid),
+        start_length: START_LENGTH,
+        start_direction: Direction::Right,
+        seed,
+        moves: game.moves().to_vec(),
+        move_times: game.move_times().to_vec(),
+        rules: *game.rules(),
+        level: game.level().clone(),
+    };
+
+    serde_json::to_writer(File::create(path)?, &replay)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+}
+
+fn run(grid: Grid, record_path: Option<String>) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock().into_raw_mode()?;
+    let mut keys = async_stdin().keys();
+    let mut seed = random_seed();
+    let mut random = XorShiftRandom::new(seed);
+    let mut events = Vec::new();
+
+    let start = Instant::now();
+    let mut game = new_game(grid);
+    write!(stdout, "{}{}", clear::All, cursor::Hide)?;
+
+    'game: loop {
+        for key in keys.by_ref() {
+            let direction = match key? {
+                Key::Char('q') | Key::Esc | Key::Ctrl('c') => break 'game,
+                Key::Char('r') => {
+                    if let GameState::Finished { .. } = game.state() {
+                        // each game gets its own seed so it can be replayed on its own
+                        seed = random_seed();
+                        random = XorShiftRandom::new(seed);
+                        game = new_game(grid);
+                    }
+                    continue;
+                },
+                Key::Char('w') | Key::Up => Direction::Up,
+                Key::Char('d') | Key::Right => Direction::Right,
+                Key::Char('s') | Key::Down => Direction::Down,
+                Key::Char('a') | Key::Left => Direction::Left,
+                _ => continue,
+            };
+            game.store_direction_change(direction);
+        }
+
+        let elapsed = start.elapsed();
+        let now = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9;
+        let previous_state = game.state();
+        let state = game.tick(now, &mut random, &mut events);
+        // there are no effects to play in the terminal
+        events.clear();
+
+        if let (GameState::Running, GameState::Finished { .. }) = (previous_state, state) {
+            if let Some(ref path) = record_path {
+                save_replay(path, &game, seed)?;
+            }
+        }
+
+        let score = game.snake().length().saturating_sub(START_LENGTH);
+        let status = match state {
+            GameState::Running => format!("Score: {}   WASD / arrows to move, q to quit", score),
+            GameState::Finished { .. } => format!("Game over! Score: {}   r to restart, q to quit", score),
+        };
+
+        write!(stdout, "{}{}{}{}", cursor::Goto(1, 1), text::render(&game), clear::CurrentLine, status)?;
+        stdout.flush()?;
+
+        thread::sleep(Duration::from_millis(FRAME_TIME_MS));
+    }
+
+    write!(stdout, "{}{}{}", clear::All, cursor::Goto(1, 1), cursor::Show)?;
+    stdout.flush()
+}
@@ -0,0 +1,74 @@
+use wrg_2d::IntVector2;
+use wrg_snake_core::{Game, GameState};
+
+const WALL: char = '#';
+const HEAD: char = '@';
+const BODY: char = 'o';
+const FOOD: char = '*';
+const FATAL: char = 'X';
+const EMPTY: char = ' ';
+
+/// Draws the game as a grid of characters surrounded by walls, each cell is
+/// two characters wide so the grid looks roughly square in most terminals.
+///
+/// Lines end in "\r\n" since the terminal is in raw mode.
+pub fn render(game: &Game) -> String {
+    let grid = game.grid();
+    let mut cells = vec![EMPTY; grid.area()];
+    let index = |position: &IntVector2| position.y as usize * grid.width as usize + position.x as usize;
+
+    for food in game.foods() {
+        cells[index(food.pos())] = FOOD;
+    }
+
+    // draw the body after food so a snake on top of food is visible,
+    // and the head last so it's visible when the snake starts coiled up
+    for position in game.snake().positions().skip(1) {
+        cells[index(position)] = BODY;
+    }
+    cells[index(&game.snake().current_position())] = HEAD;
+
+    if let GameState::Finished { cause, .. } = game.state() {
+        if let Some(position) = cause.position() {
+            cells[index(&position)] = FATAL;
+        }
+    }
+
+    let wall: String = (0..grid.width as usize * 2 + 3).map(|_| WALL).collect();
+    let mut frame = String::new();
+    frame.push_str(&wall);
+    frame.push_str("\r\n");
+
+    // y goes up in the game, but lines are printed top to bottom
+    for y in (0..grid.height as usize).rev() {
+        frame.push(WALL);
+        frame.push(' ');
+        for x in 0..grid.width as usize {
+            frame.push(cells[y * grid.width as usize + x]);
+            frame.push(' ');
+        }
+        frame.push(WALL);
+        frame.push_str("\r\n");
+    }
+
+    frame.push_str(&wall);
+    frame.push_str("\r\n");
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wrg_2d::{Direction, Grid};
+
+    #[test]
+    fn renders_snake_inside_walls() {
+        let game = Game::new(Grid::new(3, 2), IntVector2 { x: 1, y: 1 }, 2, Direction::Right, true).unwrap();
+        assert_eq!(render(&game), [
+            "#########\r\n",
+            "#   @   #\r\n",
+            "#       #\r\n",
+            "#########\r\n",
+        ].concat());
+    }
+}